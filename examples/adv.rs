@@ -69,8 +69,8 @@ fn main() {
     // Minimize cost function:
 
     //use minimize_distributed to accept SwarmConfigDistribution
-    let min = pso.minimize_distributed(job_config, swarm_config, cost_function);
+    let min = pso.minimise_distributed(job_config, swarm_config, cost_function);
 
-    println!("Minimum of: {}, With value: {:?}", min.0, min.1);
+    println!("Minimum of: {}, With value: {:?}", min.cost(), min.location());
 
 }
\ No newline at end of file
@@ -56,7 +56,7 @@ fn main() {
 
     //use minimize_independant to optimize with the default independant-swarm configuration
     //the next example will show how to use collaborative-swarms
-    let min = pso.minimize_independant(job_config, cost_function);
+    let min = pso.minimise_independant(job_config, cost_function);
 
-    println!("Minimum of: {}, With value: {:?}", min.0, min.1);
+    println!("Minimum of: {}, With value: {:?}", min.cost(), min.location());
 }
\ No newline at end of file
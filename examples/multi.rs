@@ -68,7 +68,7 @@ fn main() {
     // Minimize cost function:
 
     //use minimize to optimize with a custom SwarmConfig
-    let min = pso.minimize(job_config, swarm_config, cost_function);
+    let min = pso.minimise(job_config, swarm_config, cost_function);
 
-    println!("Minimum of: {}, With value: {:?}", min.0, min.1);
+    println!("Minimum of: {}, With value: {:?}", min.cost(), min.location());
 }
\ No newline at end of file
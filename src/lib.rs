@@ -5,7 +5,13 @@ extern crate rand;
 
 // PSO Call
 mod optimiser;
-pub use optimiser::{JobConfig, PSOConfig, ParamDist, SwarmConfig, SwarmConfigDistribution, PSO};
+pub use optimiser::{
+    AskTellSwarm, BoundaryHandling, ConsoleObserver, ConstraintHandling, Constraints, EvolutionaryState, FipsWeighting,
+    HistoryEntry, InertiaSchedule, JobConfig, LeaderSelection, NoOpObserver, Observer, OptimisationResult, PSOConfig,
+    ParamDist, ParetoRecord, ParetoResult, ParetoSwarmResult, ParticleView, PsoError, PsoRun, Record,
+    StochasticitySchedule, SwarmConfig, SwarmConfigDistribution, SwarmHistory, SwarmResult, SwarmView,
+    TerminationReason, Topology, VelocityUpdateRule, PSO,
+};
 
 // CPython Call
 extern crate cpython;
@@ -71,7 +77,7 @@ fn solve(_py: Python) -> PyResult<(f64, Vec<f64>)> {
         ParamDist::Fixed(1.45),                 // local: fixed value of 1.45
         ParamDist::Range([1.65, 0.25]),         // tribal: random value: 1.65 +/- 25% 
         ParamDist::Fixed(0.4),                  // momentum: fixed value of 0.4
-        ParamDist::Range([1.25, 0.05]),         // momentum growth factor: random value: 1.25 +/- 5%
+        ParamDist::Range([1.25, 0.05]),         // momentum growth factor (adapts the momentum): 1.25 +/- 5%
         ParamDist::Fixed(0.0125),               // wall bounce factor: fixed value of 0.0125
     );
 
@@ -81,9 +87,9 @@ fn solve(_py: Python) -> PyResult<(f64, Vec<f64>)> {
     //use minimize_distributed to accept SwarmConfigDistribution
     let min = pso.minimise_distributed(job_config, swarm_config, cost_function);
 
-    println!("Minimum of: {}, With value: {:?}", min.0, min.1);
+    println!("Minimum of: {}, With value: {:?}", min.cost(), min.location());
 
-    Ok(min)
+    Ok(min.to_tuple())
 
 }
 
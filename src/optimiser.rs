@@ -1,13 +1,36 @@
+mod history;
+mod observer;
+mod optimisation_result;
 mod optimiser_config;
+mod pareto_metrics;
+mod pareto_result;
+mod pso_error;
+mod pso_run;
 mod swarm;
 
+pub use history::{HistoryEntry, SwarmHistory};
+pub use observer::{ConsoleObserver, NoOpObserver, Observer};
+pub use optimisation_result::{OptimisationResult, SwarmResult, TerminationReason};
 pub use optimiser_config::{JobConfig, PSOConfig};
-pub use swarm::{ParamDist, SwarmConfig, SwarmConfigDistribution};
+pub use pareto_result::{ParetoResult, ParetoSwarmResult};
+pub use pso_error::PsoError;
+pub use pso_run::PsoRun;
+pub use swarm::{
+    AskTellSwarm, BoundaryHandling, ConstraintHandling, Constraints, EvolutionaryState, FipsWeighting, InertiaSchedule,
+    LeaderSelection, ParamDist, ParetoRecord, ParticleView, Record, StochasticitySchedule, SwarmConfig,
+    SwarmConfigDistribution, SwarmView, Topology, VelocityUpdateRule,
+};
 
-use rand::prelude::thread_rng;
 use std::sync::{Arc, RwLock};
 use std::thread;
-use swarm::{Bound, Record, SwarmColaborative, SwarmIndependant};
+use std::time::Instant;
+use swarm::{
+    seeded_rng, Bound, ConstraintHandler, EvaluationBudget, ParetoArchive, SwarmColaborative, SwarmIndependant,
+    SwarmPareto,
+};
+
+const MIN_OBJECTIVES: usize = 2;
+const MAX_OBJECTIVES: usize = 4;
 
 pub struct PSO {
     config: PSOConfig,
@@ -15,21 +38,17 @@ pub struct PSO {
 
 impl PSO {
     pub fn new(config: PSOConfig) -> Self {
-        if config.is_verbose {
-            println!("PSO initialised with {}", config)
-        }
-
         PSO { config }
     }
 
-    pub fn minimise_collaborative<F>(&self, job_config: JobConfig, cost_func: F) -> (f64, Vec<f64>)
+    pub fn minimise_collaborative<F>(&self, job_config: JobConfig, cost_func: F) -> OptimisationResult
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
         self.minimise(job_config, SwarmConfig::default_collab(), cost_func)
     }
 
-    pub fn minimise_independant<F>(&self, job_config: JobConfig, cost_func: F) -> (f64, Vec<f64>)
+    pub fn minimise_independant<F>(&self, job_config: JobConfig, cost_func: F) -> OptimisationResult
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
@@ -41,7 +60,20 @@ impl PSO {
         job_config: JobConfig,
         swarm_config: SwarmConfig,
         cost_func: F,
-    ) -> (f64, Vec<f64>)
+    ) -> OptimisationResult
+    where
+        F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
+    {
+        self.try_minimise(job_config, swarm_config, cost_func)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_minimise<F>(
+        &self,
+        job_config: JobConfig,
+        swarm_config: SwarmConfig,
+        cost_func: F,
+    ) -> Result<OptimisationResult, PsoError>
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
@@ -49,7 +81,7 @@ impl PSO {
             .into_iter()
             .map(|_| swarm_config.clone())
             .collect();
-        self.minimise_specific(job_config, swarm_configs, cost_func)
+        self.try_minimise_specific(job_config, swarm_configs, cost_func)
     }
 
     pub fn minimise_distributed<F>(
@@ -57,16 +89,29 @@ impl PSO {
         job_config: JobConfig,
         swarm_config_dist: SwarmConfigDistribution,
         cost_func: F,
-    ) -> (f64, Vec<f64>)
+    ) -> OptimisationResult
+    where
+        F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
+    {
+        self.try_minimise_distributed(job_config, swarm_config_dist, cost_func)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_minimise_distributed<F>(
+        &self,
+        job_config: JobConfig,
+        swarm_config_dist: SwarmConfigDistribution,
+        cost_func: F,
+    ) -> Result<OptimisationResult, PsoError>
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
-        let mut rng = thread_rng();
+        let mut rng = seeded_rng(self.config.stream_seed(0));
         let swarm_configs = (0..self.config.num_threads)
             .into_iter()
             .map(|_| swarm_config_dist.sample_configuration(&mut rng))
             .collect();
-        self.minimise_specific(job_config, swarm_configs, cost_func)
+        self.try_minimise_specific(job_config, swarm_configs, cost_func)
     }
 
     pub fn minimise_specific<F>(
@@ -74,21 +119,232 @@ impl PSO {
         job_config: JobConfig,
         swarm_configs: Vec<SwarmConfig>,
         cost_func: F,
-    ) -> (f64, Vec<f64>)
+    ) -> OptimisationResult
+    where
+        F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
+    {
+        self.try_minimise_specific(job_config, swarm_configs, cost_func)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_minimise_specific<F>(
+        &self,
+        job_config: JobConfig,
+        swarm_configs: Vec<SwarmConfig>,
+        cost_func: F,
+    ) -> Result<OptimisationResult, PsoError>
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
-        let is_collaborative_job = self.verify_swarm_configs(&swarm_configs);
+        let start = Instant::now();
+        let is_collaborative_job = self.verify_swarm_configs(&swarm_configs)?;
+        job_config.constraint_handling.verify()?;
+        let get_cost = Arc::new(cost_func);
+
+        self.config.observer.on_start(&self.config, &job_config, &swarm_configs);
+
+        let swarm_results = match is_collaborative_job {
+            true => self.run_collaborative_job(job_config, swarm_configs, get_cost)?,
+            false => self.run_independant_job(job_config, swarm_configs, get_cost)?,
+        };
+
+        let result = OptimisationResult::from_swarm_results(swarm_results, start.elapsed())?;
+        self.config.observer.on_finish(&result);
+
+        Ok(result)
+    }
+
+    pub fn start<F>(
+        &self,
+        job_config: JobConfig,
+        swarm_config: SwarmConfig,
+        cost_func: F,
+    ) -> Result<PsoRun<F>, PsoError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let swarm_configs = (0..self.config.num_threads)
+            .map(|_| swarm_config.clone())
+            .collect();
+        self.start_specific(job_config, swarm_configs, cost_func)
+    }
+
+    pub fn start_specific<F>(
+        &self,
+        job_config: JobConfig,
+        swarm_configs: Vec<SwarmConfig>,
+        cost_func: F,
+    ) -> Result<PsoRun<F>, PsoError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let is_collaborative_job = self.verify_swarm_configs(&swarm_configs)?;
+        job_config.constraint_handling.verify()?;
+        let get_cost = Arc::new(cost_func);
+        let num_variables = job_config.num_variables;
+
+        self.config.observer.on_start(&self.config, &job_config, &swarm_configs);
+
+        match is_collaborative_job {
+            true => Ok(PsoRun::new_collaborative(
+                self.collaborative_swarms(job_config, swarm_configs),
+                num_variables,
+                get_cost,
+                self.config.observer.clone(),
+            )),
+            false => Ok(PsoRun::new_independant(
+                self.independant_swarms(job_config, swarm_configs),
+                num_variables,
+                get_cost,
+                self.config.observer.clone(),
+            )),
+        }
+    }
+
+    // Multi-objective optimisation: every swarm keeps an archive of the non-dominated points it finds, and the
+    // merged archives are returned as the Pareto front; the observer is not notified of multi-objective jobs
+    pub fn minimise_pareto<F>(&self, job_config: JobConfig, swarm_config: SwarmConfig, cost_func: F) -> ParetoResult
+    where
+        F: Fn(&[f64]) -> Vec<f64> + Sync + Send + 'static,
+    {
+        self.try_minimise_pareto(job_config, swarm_config, cost_func)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_minimise_pareto<F>(
+        &self,
+        job_config: JobConfig,
+        swarm_config: SwarmConfig,
+        cost_func: F,
+    ) -> Result<ParetoResult, PsoError>
+    where
+        F: Fn(&[f64]) -> Vec<f64> + Sync + Send + 'static,
+    {
+        let start = Instant::now();
+        let swarm_configs: Vec<SwarmConfig> = (0..self.config.num_threads)
+            .map(|_| swarm_config.clone())
+            .collect();
+        let is_collaborative_job = self.verify_swarm_configs(&swarm_configs)?;
+        swarm_config.verify_multi_objective()?;
+        if !job_config.constraints.is_empty() {
+            return Err(PsoError::MultiObjectiveUnsupported("constraints".to_string()));
+        }
+        if job_config.archive_size == 0 {
+            return Err(PsoError::EmptyParetoArchive);
+        }
+        if job_config.leader_selection == (LeaderSelection::Grid { divisions: 0 }) {
+            return Err(PsoError::NoGridDivisions);
+        }
+
         let get_cost = Arc::new(cost_func);
+        let budget = EvaluationBudget::new(job_config.max_evaluations);
 
-        if self.config.is_verbose {
-            println!("Job Starting... \n {}", job_config)
+        // the number of objectives is taken from one evaluation at the centre of the search space; without the
+        // budget for it, no point of the job is ever evaluated, so there are no objectives to count
+        let centre: Vec<f64> =
+            job_config.pos_bounds.range_vec().iter().map(|[lower, upper]| 0.5 * (lower + upper)).collect();
+        let centre_record = match budget.try_take() {
+            true => Some(ParetoRecord::new(get_cost(&centre), &centre)),
+            false => None,
+        };
+        let num_objectives = match centre_record.as_ref() {
+            Some(record) => record.costs().len(),
+            None => MIN_OBJECTIVES,
+        };
+        if !(MIN_OBJECTIVES..=MAX_OBJECTIVES).contains(&num_objectives) {
+            return Err(PsoError::ObjectiveCountOutOfRange {
+                min: MIN_OBJECTIVES,
+                max: MAX_OBJECTIVES,
+                found: num_objectives,
+            });
         }
+        let global_archive = match is_collaborative_job {
+            true => Some(Arc::new(RwLock::new(ParetoArchive::new(
+                job_config.archive_size,
+                job_config.leader_selection,
+            )))),
+            false => None,
+        };
+
+        let swarm_threads: Vec<_> = swarm_configs
+            .iter()
+            .enumerate()
+            .map(|(i, swarm_config)| {
+                let mut swarm = SwarmPareto::new(
+                    i,
+                    swarm_config,
+                    job_config.clone(),
+                    &self.config,
+                    budget.clone(),
+                    num_objectives,
+                );
+                let global_archive_ref = global_archive.clone();
+                let get_cost_ref = get_cost.clone();
+
+                thread::Builder::new()
+                    .name(format!("swarm_thread_{}", i))
+                    .spawn(move || swarm.run(get_cost_ref, global_archive_ref))
+            })
+            .collect();
 
-        return match is_collaborative_job {
-            true => self.run_collaborative_job(job_config, swarm_configs, get_cost),
-            false => self.run_independant_job(job_config, swarm_configs, get_cost),
+        let swarm_results = join_swarm_threads(swarm_threads)?.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+        let mut rng = seeded_rng(self.config.stream_seed(0));
+        let mut front = ParetoArchive::new(job_config.archive_size, job_config.leader_selection);
+        let centre_evaluations = match centre_record {
+            Some(record) => {
+                front.insert(record, &mut rng);
+                1
+            }
+            None => 0,
         };
+        for sr in swarm_results.iter() {
+            for record in sr.front.iter() {
+                front.insert(record.clone(), &mut rng);
+            }
+        }
+
+        let mut result = ParetoResult::from_swarm_results(swarm_results, front.members().to_vec(), start.elapsed())?;
+        result.evaluations += centre_evaluations;
+        Ok(result)
+    }
+
+    fn collaborative_swarms(&self, job_config: JobConfig, swarm_configs: Vec<SwarmConfig>) -> Vec<SwarmColaborative> {
+        let budget = EvaluationBudget::new(job_config.max_evaluations);
+        let constraint_handler = ConstraintHandler::new(&job_config.constraints, job_config.constraint_handling);
+        swarm_configs
+            .iter()
+            .enumerate()
+            .map(|(i, swarm_config)| {
+                SwarmColaborative::new(
+                    i,
+                    swarm_config,
+                    job_config.clone(),
+                    &self.config,
+                    budget.clone(),
+                    constraint_handler.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn independant_swarms(&self, job_config: JobConfig, swarm_configs: Vec<SwarmConfig>) -> Vec<SwarmIndependant> {
+        let budget = EvaluationBudget::new(job_config.max_evaluations);
+        let constraint_handler = ConstraintHandler::new(&job_config.constraints, job_config.constraint_handling);
+        swarm_configs
+            .iter()
+            .enumerate()
+            .map(|(i, swarm_config)| {
+                SwarmIndependant::new(
+                    i,
+                    swarm_config,
+                    job_config.clone(),
+                    &self.config,
+                    budget.clone(),
+                    constraint_handler.clone(),
+                )
+            })
+            .collect()
     }
 
     fn run_collaborative_job<F>(
@@ -96,47 +352,27 @@ impl PSO {
         job_config: JobConfig,
         swarm_configs: Vec<SwarmConfig>,
         get_cost: Arc<F>,
-    ) -> (f64, Vec<f64>)
+    ) -> Result<Vec<SwarmResult>, PsoError>
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
         let global_record = Arc::new(RwLock::new(Record::blank(job_config.num_variables)));
 
-        let swarm_threads: Vec<Result<std::thread::JoinHandle<()>, std::io::Error>> =
-            (0..self.config.num_threads)
-                .into_iter()
-                .map(|i| {
-                    let mut swarm = SwarmColaborative::new(
-                        format!("Swarm_{}", i),
-                        &swarm_configs[i],
-                        self.config.uce,
-                        self.config.nppt,
-                    );
-                    let mut global_record_ref = global_record.clone();
-                    let get_cost_ref = get_cost.clone();
-                    let job_config_clone = job_config.clone();
-
-                    thread::Builder::new()
-                        .name(format!("swarm_thread_{}", i))
-                        .spawn(move || {
-                            swarm.run(job_config_clone, get_cost_ref, &mut global_record_ref);
-                        })
-                })
-                .collect();
-
-        for st in swarm_threads {
-            match st {
-                Ok(handle) => handle.join().expect("Thread Panicked during Job!"),
-                Err(msg) => println!("Unable to join thread: {}", msg),
-            }
-        }
+        let swarm_threads: Vec<Result<std::thread::JoinHandle<SwarmResult>, std::io::Error>> = self
+            .collaborative_swarms(job_config, swarm_configs)
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut swarm)| {
+                let global_record_ref = global_record.clone();
+                let get_cost_ref = get_cost.clone();
 
-        let minimum = global_record.read().unwrap();
-        if self.config.is_verbose {
-            println!("Minimum {}", minimum)
-        }
+                thread::Builder::new()
+                    .name(format!("swarm_thread_{}", i))
+                    .spawn(move || -> SwarmResult { swarm.run(get_cost_ref, global_record_ref) })
+            })
+            .collect();
 
-        (*minimum).to_tuple()
+        join_swarm_threads(swarm_threads)
     }
 
     fn run_independant_job<F>(
@@ -144,78 +380,71 @@ impl PSO {
         job_config: JobConfig,
         swarm_configs: Vec<SwarmConfig>,
         get_cost: Arc<F>,
-    ) -> (f64, Vec<f64>)
+    ) -> Result<Vec<SwarmResult>, PsoError>
     where
         F: Fn(&[f64]) -> f64 + Sync + Send + 'static,
     {
-        let mut minimum = Record::blank(job_config.num_variables);
-
-        let swarm_threads: Vec<Result<std::thread::JoinHandle<Record>, std::io::Error>> =
-            (0..self.config.num_threads)
-                .into_iter()
-                .map(|i| {
-                    let mut swarm = SwarmIndependant::new(
-                        format!("Swarm_{}", i),
-                        &swarm_configs[i],
-                        self.config.uce,
-                        self.config.nppt,
-                    );
-                    let get_cost_ref = get_cost.clone();
-                    let job_config_clone = job_config.clone();
-
-                    thread::Builder::new()
-                        .name(format!("swarm_thread_{}", i))
-                        .spawn(move || -> Record { swarm.run(job_config_clone, get_cost_ref) })
-                })
-                .collect();
-
-        for st in swarm_threads {
-            match st {
-                Ok(handle) => {
-                    let thread_record = handle.join().expect("Thread Panicked during Operation!");
-                    minimum.blind_accumulate(&thread_record);
-                }
-                Err(msg) => println!("Unable to join thread: {}", msg),
-            }
-        }
+        let swarm_threads: Vec<Result<std::thread::JoinHandle<SwarmResult>, std::io::Error>> = self
+            .independant_swarms(job_config, swarm_configs)
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut swarm)| {
+                let get_cost_ref = get_cost.clone();
 
-        if self.config.is_verbose {
-            println!("Minimum {}", minimum)
-        }
+                thread::Builder::new()
+                    .name(format!("swarm_thread_{}", i))
+                    .spawn(move || -> SwarmResult { swarm.run(get_cost_ref) })
+            })
+            .collect();
 
-        minimum.to_tuple()
+        join_swarm_threads(swarm_threads)
     }
 
-    fn verify_swarm_configs(&self, swarm_configs: &Vec<SwarmConfig>) -> bool {
-        assert_eq!(
-            self.config.num_threads,
-            swarm_configs.len(),
-            "One Swarm Configuration must be provided for each swarm-thread!"
-        );
+    fn verify_swarm_configs(&self, swarm_configs: &[SwarmConfig]) -> Result<bool, PsoError> {
+        for swarm_config in swarm_configs.iter() {
+            swarm_config.verify()?;
+        }
+
+        if self.config.num_threads != swarm_configs.len() {
+            return Err(PsoError::SwarmConfigCountMismatch {
+                expected: self.config.num_threads,
+                found: swarm_configs.len(),
+            });
+        }
 
         if self.config.num_threads == 1 {
-            assert!(
-                !swarm_configs[0].is_collaborative(),
-                "Single-Swarm Optimisation may not use a Collaborative Swarm Configuration!"
-            );
-            if self.config.is_verbose {
-                println!("Swarm Configuration: \n \t {}", swarm_configs[0])
+            if swarm_configs[0].is_collaborative() {
+                return Err(PsoError::CollaborativeSingleSwarm);
             }
-            return false;
+            Ok(false)
         } else {
-            if self.config.is_verbose {
-                println!("Swarm Configurations: \n \t 0: {}", swarm_configs[0])
-            }
             let is_collaborative_opt = swarm_configs[0].is_collaborative();
-            for i in 1..self.config.num_threads {
-                assert_eq!(is_collaborative_opt, swarm_configs[i].is_collaborative(),
-                    "All swarm configurations must have the same Collaborative or Independant status!"
-                );
-                if self.config.is_verbose {
-                    println!("\t {}: {}", i, swarm_configs[i])
-                }
+            if swarm_configs.iter().any(|sc| sc.is_collaborative() != is_collaborative_opt) {
+                return Err(PsoError::MixedSwarmConfigs);
             }
-            return is_collaborative_opt;
+            Ok(is_collaborative_opt)
         }
     }
-}
\ No newline at end of file
+}
+
+// waits for every swarm-thread, then reports the first that could not be spawned or panicked
+fn join_swarm_threads<T>(
+    swarm_threads: Vec<Result<thread::JoinHandle<T>, std::io::Error>>,
+) -> Result<Vec<T>, PsoError> {
+    let joined: Vec<Result<T, PsoError>> = swarm_threads
+        .into_iter()
+        .map(|st| match st {
+            Ok(handle) => handle.join().map_err(|panic| {
+                let msg = match (panic.downcast_ref::<&str>(), panic.downcast_ref::<String>()) {
+                    (Some(msg), _) => msg.to_string(),
+                    (None, Some(msg)) => msg.clone(),
+                    (None, None) => String::from("thread panicked"),
+                };
+                PsoError::SwarmThreadFailed(msg)
+            }),
+            Err(msg) => Err(PsoError::SwarmThreadFailed(msg.to_string())),
+        })
+        .collect();
+
+    joined.into_iter().collect()
+}
@@ -0,0 +1,97 @@
+use std::fmt::Write;
+
+const CSV_HEADER: &str = "itteration,tribal_best,global_best,mean_cost,diversity,inertia";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub itteration: usize,
+    pub tribal_best: f64,
+    pub global_best: f64,
+    // None when no particle was evaluated during the itteration
+    pub mean_cost: Option<f64>,
+    pub diversity: f64,
+    pub inertia: f64,
+}
+
+impl HistoryEntry {
+    fn csv_row(&self) -> String {
+        format!("{},{},{},{},{},{}",
+            self.itteration,
+            self.tribal_best,
+            self.global_best,
+            self.mean_cost.map_or(String::new(), |mean_cost| mean_cost.to_string()),
+            self.diversity,
+            self.inertia,
+        )
+    }
+
+    fn json_object(&self) -> String {
+        format!("{{\"itteration\":{},\"tribal_best\":{},\"global_best\":{},\"mean_cost\":{},\"diversity\":{},\"inertia\":{}}}",
+            self.itteration,
+            json_number(self.tribal_best),
+            json_number(self.global_best),
+            self.mean_cost.map_or(String::from("null"), json_number),
+            json_number(self.diversity),
+            json_number(self.inertia),
+        )
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SwarmHistory {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl SwarmHistory {
+    pub fn new() -> Self {
+        SwarmHistory { entries: Vec::new() }
+    }
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries.push(entry);
+    }
+
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("{}\n", CSV_HEADER);
+        for entry in self.entries.iter() {
+            writeln!(csv, "{}", entry.csv_row()).unwrap();
+        }
+        csv
+    }
+
+    pub fn to_json(&self) -> String {
+        let objects: Vec<String> = self.entries.iter().map(|entry| entry.json_object()).collect();
+        format!("[{}]", objects.join(","))
+    }
+}
+
+// histories of several swarms in one table, keyed by swarm name
+pub fn histories_to_csv(histories: &[(&str, &SwarmHistory)]) -> String {
+    let mut csv = format!("swarm,{}\n", CSV_HEADER);
+    for (name, history) in histories.iter() {
+        for entry in history.entries.iter() {
+            writeln!(csv, "{},{}", name, entry.csv_row()).unwrap();
+        }
+    }
+    csv
+}
+
+pub fn histories_to_json(histories: &[(&str, &SwarmHistory)]) -> String {
+    let fields: Vec<String> = histories
+        .iter()
+        .map(|(name, history)| format!("{}:{}", json_string(name), history.to_json()))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+// JSON has no representation for NaN or infinity
+pub(super) fn json_number(value: f64) -> String {
+    match value.is_finite() {
+        true => format!("{}", value),
+        false => String::from("null"),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
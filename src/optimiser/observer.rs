@@ -0,0 +1,126 @@
+use std::fmt;
+use std::sync::Arc;
+
+use super::{JobConfig, OptimisationResult, PSOConfig, Record, SwarmConfig, SwarmView};
+
+// Observers are shared by every swarm-thread of a job, so any state they keep needs interior mutability
+pub trait Observer: Send + Sync {
+    fn on_start(&self, _pso_config: &PSOConfig, _job_config: &JobConfig, _swarm_configs: &[SwarmConfig]) {}
+
+    fn on_iteration(&self, _swarm: &SwarmView) {}
+
+    fn on_new_tribal_record(&self, _swarm_name: &str, _itteration: usize, _record: &Record) {}
+
+    fn on_globalise(&self, _swarm_name: &str, _itteration: usize, _global_record: &Record) {}
+
+    fn on_finish(&self, _result: &OptimisationResult) {}
+}
+
+// lets the caller keep a handle on an observer (e.g. to read collected metrics) after handing it to PSOConfig
+impl<O> Observer for Arc<O>
+where
+    O: Observer + ?Sized,
+{
+    fn on_start(&self, pso_config: &PSOConfig, job_config: &JobConfig, swarm_configs: &[SwarmConfig]) {
+        (**self).on_start(pso_config, job_config, swarm_configs)
+    }
+
+    fn on_iteration(&self, swarm: &SwarmView) {
+        (**self).on_iteration(swarm)
+    }
+
+    fn on_new_tribal_record(&self, swarm_name: &str, itteration: usize, record: &Record) {
+        (**self).on_new_tribal_record(swarm_name, itteration, record)
+    }
+
+    fn on_globalise(&self, swarm_name: &str, itteration: usize, global_record: &Record) {
+        (**self).on_globalise(swarm_name, itteration, global_record)
+    }
+
+    fn on_finish(&self, result: &OptimisationResult) {
+        (**self).on_finish(result)
+    }
+}
+
+impl fmt::Debug for dyn Observer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Observer")
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct NoOpObserver;
+
+impl Observer for NoOpObserver {}
+
+#[derive(Clone, Debug)]
+pub struct ConsoleObserver {
+    uce: Option<usize>,
+    is_verbose: bool,
+}
+
+impl ConsoleObserver {
+    pub fn new(update_console_every: usize, verbose: bool) -> Self {
+        let uce = match update_console_every == 0 {
+            true => None,
+            false => Some(update_console_every),
+        };
+
+        ConsoleObserver { uce, is_verbose: verbose }
+    }
+
+    pub fn is_silent(&self) -> bool {
+        self.uce.is_none() && !self.is_verbose
+    }
+}
+
+impl Observer for ConsoleObserver {
+    fn on_start(&self, pso_config: &PSOConfig, job_config: &JobConfig, swarm_configs: &[SwarmConfig]) {
+        if self.is_verbose {
+            println!("PSO initialised with {}", pso_config);
+            println!("Job Starting... \n {}", job_config);
+
+            match swarm_configs.len() {
+                1 => println!("Swarm Configuration: \n \t {}", swarm_configs[0]),
+                _ => {
+                    println!("Swarm Configurations:");
+                    for (i, swarm_config) in swarm_configs.iter().enumerate() {
+                        println!("\t {}: {}", i, swarm_config)
+                    }
+                }
+            }
+        }
+    }
+
+    fn on_iteration(&self, swarm: &SwarmView) {
+        if let Some(update_period) = self.uce {
+            if swarm.itteration().is_multiple_of(update_period) {
+                match swarm.is_collaborative() {
+                    true => println!(
+                        "{} iter {}: \t c = {}, w = {} \n \t Tribal {} \n \t Global {}",
+                        swarm.name(),
+                        swarm.itteration(),
+                        swarm.stall_count(),
+                        swarm.inertia(),
+                        swarm.tribal_record(),
+                        swarm.global_record()
+                    ),
+                    false => println!(
+                        "{} iter {}: \t c = {}, w = {} \n \t {} ",
+                        swarm.name(),
+                        swarm.itteration(),
+                        swarm.stall_count(),
+                        swarm.inertia(),
+                        swarm.tribal_record(),
+                    ),
+                }
+            }
+        }
+    }
+
+    fn on_finish(&self, result: &OptimisationResult) {
+        if self.is_verbose {
+            println!("Minimum {}", result)
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt;
+use std::time::Duration;
+
+use super::history::{histories_to_csv, histories_to_json};
+use super::{PsoError, Record, SwarmHistory};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TerminationReason {
+    ExitCost,
+    MaxItterations,
+    EvaluationBudget,
+    Stalled,
+    DiversityCollapsed,
+    Cancelled,
+    TimedOut,
+    Stopped,
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ExitCost => write!(f, "exit cost reached"),
+            Self::MaxItterations => write!(f, "max itterations reached"),
+            Self::EvaluationBudget => write!(f, "evaluation budget spent"),
+            Self::Stalled => write!(f, "stalled without improvement"),
+            Self::DiversityCollapsed => write!(f, "swarm diversity collapsed"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::TimedOut => write!(f, "deadline passed"),
+            Self::Stopped => write!(f, "stopped before completion"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SwarmResult {
+    pub name: String,
+    pub record: Record,
+    pub termination: TerminationReason,
+    pub itterations: usize,
+    pub evaluations: usize,
+    pub inertia: f64,
+    pub duration: Duration,
+    pub history: Option<SwarmHistory>,
+}
+
+impl fmt::Display for SwarmResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} stopped after {} itterations ({}), {} evaluations in {:?}, final w = {}: \n \t {}",
+            self.name,
+            self.itterations,
+            self.termination,
+            self.evaluations,
+            self.duration,
+            self.inertia,
+            self.record,
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OptimisationResult {
+    pub best: Record,
+    pub best_swarm: usize,
+    pub termination: TerminationReason,
+    pub swarm_results: Vec<SwarmResult>,
+    pub evaluations: usize,
+    pub duration: Duration,
+}
+
+impl OptimisationResult {
+    pub fn from_swarm_results(swarm_results: Vec<SwarmResult>, duration: Duration) -> Result<Self, PsoError> {
+        if swarm_results.is_empty() {
+            return Err(PsoError::NoSwarmResults);
+        }
+
+        let mut best_swarm = 0;
+        for (i, sr) in swarm_results.iter().enumerate() {
+            if sr.record < swarm_results[best_swarm].record {
+                best_swarm = i;
+            }
+        }
+
+        Ok(OptimisationResult {
+            best: swarm_results[best_swarm].record.clone(),
+            best_swarm,
+            termination: swarm_results[best_swarm].termination,
+            evaluations: swarm_results.iter().map(|sr| sr.evaluations).sum(),
+            swarm_results,
+            duration,
+        })
+    }
+
+    pub fn cost(&self) -> f64 {
+        self.best.get_cost()
+    }
+
+    pub fn location(&self) -> Vec<f64> {
+        self.best.get_location()
+    }
+
+    pub fn swarm_records(&self) -> Vec<Record> {
+        self.swarm_results.iter().map(|sr| sr.record.clone()).collect()
+    }
+
+    pub fn to_tuple(&self) -> (f64, Vec<f64>) {
+        self.best.to_tuple()
+    }
+
+    pub fn history_to_csv(&self) -> Option<String> {
+        self.histories().map(|histories| histories_to_csv(&histories))
+    }
+
+    pub fn history_to_json(&self) -> Option<String> {
+        self.histories().map(|histories| histories_to_json(&histories))
+    }
+
+    // None unless every swarm recorded its history
+    fn histories(&self) -> Option<Vec<(&str, &SwarmHistory)>> {
+        self.swarm_results
+            .iter()
+            .map(|sr| sr.history.as_ref().map(|history| (sr.name.as_str(), history)))
+            .collect()
+    }
+}
+
+impl fmt::Display for OptimisationResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} found by {} ({}), {} evaluations in {:?}",
+            self.best,
+            self.swarm_results[self.best_swarm].name,
+            self.termination,
+            self.evaluations,
+            self.duration,
+        )
+    }
+}
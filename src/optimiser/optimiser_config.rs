@@ -1,40 +1,91 @@
 use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use super::Bound;
+use super::{
+    Bound, BoundaryHandling, ConsoleObserver, ConstraintHandling, Constraints, LeaderSelection, NoOpObserver, Observer,
+    PsoError, TerminationReason,
+};
 
 const MAX_THREADS: usize = 32;
 const MAX_VARIABLES: usize = 128;
+const DEFAULT_ARCHIVE_SIZE: usize = 100;
 
 #[derive(Clone, Debug)]
 pub struct PSOConfig {
     pub num_threads: usize,
     pub nppt: usize,
-    pub uce: Option<usize>,
-    pub is_verbose: bool
+    pub seed: Option<u64>,
+    pub observer: Arc<dyn Observer>,
+    pub record_history: bool,
 }
 
 impl PSOConfig {
     pub fn new(num_swarms: usize, num_particles_per_thread: usize, update_console_every: usize, verbose: bool) -> Self {
-        assert!(num_swarms <= MAX_THREADS, "Number of Swarms must not exceed {}!", MAX_THREADS);
-        assert!(num_swarms > 0, "There must be at least one Swarm");
+        Self::try_new(num_swarms, num_particles_per_thread, update_console_every, verbose)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(
+        num_swarms: usize,
+        num_particles_per_thread: usize,
+        update_console_every: usize,
+        verbose: bool,
+    ) -> Result<Self, PsoError> {
+        if num_swarms > MAX_THREADS {
+            return Err(PsoError::TooManySwarms { max: MAX_THREADS, found: num_swarms });
+        }
+        if num_swarms == 0 {
+            return Err(PsoError::NoSwarms);
+        }
 
-        let uce = match update_console_every == 0 {
-            true => None,
-            false => Some(update_console_every),
+        let console = ConsoleObserver::new(update_console_every, verbose);
+        let observer: Arc<dyn Observer> = match console.is_silent() {
+            true => Arc::new(NoOpObserver),
+            false => Arc::new(console),
         };
 
-        PSOConfig{
+        Ok(PSOConfig{
             num_threads: num_swarms, 
             nppt: num_particles_per_thread, 
-            uce: uce,
-            is_verbose: verbose,
-        }
+            seed: None,
+            observer,
+            record_history: false,
+        })
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_history(mut self) -> Self {
+        self.record_history = true;
+        self
+    }
+
+    pub fn with_observer<O>(mut self, observer: O) -> Self
+    where
+        O: Observer + 'static,
+    {
+        self.observer = Arc::new(observer);
+        self
+    }
+
+    // each swarm (and the configuration sampler, stream 0) gets its own seed derived from the job seed
+    pub fn stream_seed(&self, stream: usize) -> Option<u64> {
+        self.seed.map(|seed| seed ^ (stream as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15))
     }
 }
 
 impl fmt::Display for PSOConfig {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{} swarm-threads of {} particles each \n", self.num_threads, self.nppt)
+        write!(f, "{} swarm-threads of {} particles each", self.num_threads, self.nppt)?;
+        match self.seed {
+            Some(seed) => writeln!(f, ", seeded with {} ", seed),
+            None => writeln!(f, " "),
+        }
     }
 }
 
@@ -43,8 +94,19 @@ pub struct JobConfig{
     pub num_variables: usize,
     pub pos_bounds: Bound,
     pub vel_bounds: Bound,
+    pub boundary_handling: BoundaryHandling,
     pub max_itterations: usize,
     pub exit_cost: f64,
+    pub max_evaluations: Option<usize>,
+    pub stall_itterations: Option<usize>,
+    pub stall_tolerance: f64,
+    pub min_diversity: Option<f64>,
+    pub archive_size: usize,
+    pub leader_selection: LeaderSelection,
+    pub constraints: Constraints,
+    pub constraint_handling: ConstraintHandling,
+    pub deadline: Option<Instant>,
+    pub cancel_token: Option<Arc<AtomicBool>>,
 }
 
 impl JobConfig {
@@ -55,14 +117,128 @@ impl JobConfig {
         max_itterations: usize,
         exit_cost: f64,
     ) -> Self {
-        assert!(num_variables <= MAX_VARIABLES, "Number of variables must not exceed {}!", MAX_VARIABLES);
-        assert_eq!(num_variables, variable_bounds.len(), "Variable Bounds Vector must have one entry for each variable!");
-        assert_eq!(num_variables, max_velocities.len(), "Max Velociteis Vector must have one entry for each variable!");
+        Self::try_new(num_variables, variable_bounds, max_velocities, max_itterations, exit_cost)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new(
+        num_variables: usize, 
+        variable_bounds: Vec<[f64;2]>, 
+        max_velocities: Vec<f64>, 
+        max_itterations: usize,
+        exit_cost: f64,
+    ) -> Result<Self, PsoError> {
+        if num_variables > MAX_VARIABLES {
+            return Err(PsoError::TooManyVariables { max: MAX_VARIABLES, found: num_variables });
+        }
+        if num_variables == 0 {
+            return Err(PsoError::NoVariables);
+        }
+        if num_variables != variable_bounds.len() {
+            return Err(PsoError::BoundsLengthMismatch { expected: num_variables, found: variable_bounds.len() });
+        }
+        if num_variables != max_velocities.len() {
+            return Err(PsoError::VelocitiesLengthMismatch { expected: num_variables, found: max_velocities.len() });
+        }
 
-        let pos_bounds = Bound::from_upper_lower(variable_bounds);
-        let vel_bounds = Bound::from_max(max_velocities);
+        let pos_bounds = Bound::from_upper_lower(variable_bounds)?;
+        let vel_bounds = Bound::from_max(max_velocities)?;
 
-        JobConfig {num_variables, pos_bounds, vel_bounds, max_itterations, exit_cost}
+        Ok(JobConfig {
+            num_variables,
+            pos_bounds,
+            vel_bounds,
+            boundary_handling: BoundaryHandling::default(),
+            max_itterations,
+            exit_cost,
+            max_evaluations: None,
+            stall_itterations: None,
+            stall_tolerance: 0.0,
+            min_diversity: None,
+            archive_size: DEFAULT_ARCHIVE_SIZE,
+            leader_selection: LeaderSelection::default(),
+            constraints: Constraints::default(),
+            constraint_handling: ConstraintHandling::default(),
+            deadline: None,
+            cancel_token: None,
+        })
+    }
+
+    pub fn with_boundary_handling(mut self, boundary_handling: BoundaryHandling) -> Self {
+        self.boundary_handling = boundary_handling;
+        self
+    }
+
+    // total cost-function calls allowed across every swarm of the job
+    pub fn with_max_evaluations(mut self, max_evaluations: usize) -> Self {
+        self.max_evaluations = Some(max_evaluations);
+        self
+    }
+
+    // a swarm stalls once its best record has not improved by more than tolerance * |best cost|, or by more than
+    // tolerance * best violation while it is infeasible, for the given number of consecutive itterations
+    pub fn with_stall_limit(mut self, itterations: usize, tolerance: f64) -> Self {
+        self.stall_itterations = Some(itterations);
+        self.stall_tolerance = tolerance;
+        self
+    }
+
+    // stop a swarm once the mean distance of its particles from their centroid drops below min_diversity
+    pub fn with_min_diversity(mut self, min_diversity: f64) -> Self {
+        self.min_diversity = Some(min_diversity);
+        self
+    }
+
+    // only used by multi-objective jobs: the most non-dominated points each archive keeps,
+    // and how leaders are drawn from it
+    pub fn with_pareto_archive(mut self, archive_size: usize, leader_selection: LeaderSelection) -> Self {
+        self.archive_size = archive_size;
+        self.leader_selection = leader_selection;
+        self
+    }
+
+    // adds an inequality constraint g(x) <= 0; positive values of g are the amount by which a point breaks it
+    pub fn with_constraint<G>(mut self, constraint: G) -> Self
+    where
+        G: Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    {
+        self.constraints.push(constraint);
+        self
+    }
+
+    pub fn with_constraint_handling(mut self, constraint_handling: ConstraintHandling) -> Self {
+        self.constraint_handling = constraint_handling;
+        self
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    // the time limit starts counting when it is set, not when the job starts
+    pub fn with_time_limit(self, time_limit: Duration) -> Self {
+        self.with_deadline(Instant::now() + time_limit)
+    }
+
+    // swarms stop at the end of the itteration in which the token is set to true
+    pub fn with_cancel_token(mut self, cancel_token: Arc<AtomicBool>) -> Self {
+        self.cancel_token = Some(cancel_token);
+        self
+    }
+
+    pub fn interruption(&self) -> Option<TerminationReason> {
+        if let Some(cancel_token) = &self.cancel_token {
+            if cancel_token.load(Ordering::Relaxed) {
+                return Some(TerminationReason::Cancelled);
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return Some(TerminationReason::TimedOut);
+            }
+        }
+        None
     }
 
     //add constructor with auto max_vel
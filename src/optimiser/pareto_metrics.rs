@@ -0,0 +1,181 @@
+use std::fmt::Write;
+
+use super::history::json_number;
+use super::{ParetoRecord, PsoError};
+
+// volume of objective space dominated by the front and bounded by the reference point; points that do not
+// dominate the reference contribute nothing
+pub fn hypervolume(front: &[ParetoRecord], reference: &[f64]) -> Result<f64, PsoError> {
+    check_objective_counts(front, std::iter::once(reference))?;
+
+    let points: Vec<Vec<f64>> = front
+        .iter()
+        .filter(|record| record.costs().iter().zip(reference).all(|(cost, limit)| cost < limit))
+        .map(|record| record.costs().to_vec())
+        .collect();
+
+    Ok(sliced_volume(points, reference))
+}
+
+// slices the volume along the last objective, each slice being the hypervolume of the points below it
+fn sliced_volume(mut points: Vec<Vec<f64>>, reference: &[f64]) -> f64 {
+    if points.is_empty() {
+        return 0.0;
+    }
+
+    let m = reference.len() - 1;
+    if m == 0 {
+        return reference[0] - points.iter().map(|p| p[0]).fold(f64::INFINITY, f64::min);
+    }
+
+    points.sort_by(|a, b| a[m].partial_cmp(&b[m]).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut volume = 0.0;
+    for i in 0..points.len() {
+        let upper = match i + 1 < points.len() {
+            true => points[i + 1][m],
+            false => reference[m],
+        };
+        if upper > points[i][m] {
+            let below: Vec<Vec<f64>> = points[..=i].iter().map(|p| p[..m].to_vec()).collect();
+            volume += sliced_volume(below, &reference[..m]) * (upper - points[i][m]);
+        }
+    }
+    volume
+}
+
+// mean distance, in objective space, from each point of the true front to its nearest point of the front
+pub fn inverted_generational_distance(front: &[ParetoRecord], true_front: &[Vec<f64>]) -> Result<f64, PsoError> {
+    check_objective_counts(front, true_front.iter().map(|point| point.as_slice()))?;
+    if front.is_empty() {
+        return Ok(f64::INFINITY);
+    }
+    if true_front.is_empty() {
+        return Ok(0.0);
+    }
+
+    Ok(true_front.iter().map(|target| nearest(target, front.iter().map(|record| record.costs()))).sum::<f64>()
+        / true_front.len() as f64)
+}
+
+// Schott's spacing: standard deviation of each point's manhattan distance to its nearest neighbour, zero when
+// the points are evenly spaced
+pub fn spacing(front: &[ParetoRecord]) -> Result<f64, PsoError> {
+    check_objective_counts(front, std::iter::empty())?;
+    if front.len() < 2 {
+        return Ok(0.0);
+    }
+
+    let gaps: Vec<f64> = front
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            front
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, b)| a.costs().iter().zip(b.costs()).map(|(x, y)| (x - y).abs()).sum::<f64>())
+                .fold(f64::INFINITY, f64::min)
+        })
+        .collect();
+
+    let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+    Ok((gaps.iter().map(|gap| (gap - mean).powi(2)).sum::<f64>() / (gaps.len() - 1) as f64).sqrt())
+}
+
+// generalised spread (Deb's delta): how far the front is from reaching the extremes of the true front with
+// evenly spaced points, zero for an ideal distribution
+pub fn spread(front: &[ParetoRecord], true_front: &[Vec<f64>]) -> Result<f64, PsoError> {
+    check_objective_counts(front, true_front.iter().map(|point| point.as_slice()))?;
+    if front.len() < 2 || true_front.is_empty() {
+        return Ok(1.0);
+    }
+
+    let num_objectives = front[0].costs().len();
+    let extremes: f64 = (0..num_objectives)
+        .map(|m| {
+            let extreme = true_front
+                .iter()
+                .fold(&true_front[0], |extreme, point| match point[m] > extreme[m] {
+                    true => point,
+                    false => extreme,
+                });
+            nearest(extreme, front.iter().map(|record| record.costs()))
+        })
+        .sum();
+
+    let gaps: Vec<f64> = front
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            nearest(
+                a.costs(),
+                front.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, b)| b.costs()),
+            )
+        })
+        .collect();
+    let mean = gaps.iter().sum::<f64>() / gaps.len() as f64;
+
+    match extremes + gaps.len() as f64 * mean > 0.0 {
+        true => Ok((extremes + gaps.iter().map(|gap| (gap - mean).abs()).sum::<f64>())
+            / (extremes + gaps.len() as f64 * mean)),
+        false => Ok(0.0),
+    }
+}
+
+// every cost vector, of the front and of the reference points, must have as many objectives as the first
+fn check_objective_counts<'a, I>(front: &'a [ParetoRecord], references: I) -> Result<(), PsoError>
+where
+    I: Iterator<Item = &'a [f64]>,
+{
+    let mut costs = front.iter().map(|record| record.costs()).chain(references);
+    if let Some(first) = costs.next() {
+        let expected = first.len();
+        if let Some(point) = costs.find(|point| point.len() != expected) {
+            return Err(PsoError::ReferencePointMismatch { expected, found: point.len() });
+        }
+    }
+    Ok(())
+}
+
+fn nearest<'a, I: Iterator<Item = &'a [f64]>>(target: &[f64], points: I) -> f64 {
+    points
+        .map(|point| target.iter().zip(point).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt())
+        .fold(f64::INFINITY, f64::min)
+}
+
+// one row per point: its location, then its costs
+pub fn front_to_csv(front: &[ParetoRecord]) -> String {
+    let num_variables = front.first().map_or(0, |record| record.location().len());
+    let num_objectives = front.first().map_or(0, |record| record.costs().len());
+
+    let header: Vec<String> = (0..num_variables)
+        .map(|i| format!("location_{}", i))
+        .chain((0..num_objectives).map(|m| format!("cost_{}", m)))
+        .collect();
+
+    let mut csv = format!("{}\n", header.join(","));
+    for record in front.iter() {
+        let row: Vec<String> = record.location().iter().chain(record.costs()).map(|value| value.to_string()).collect();
+        writeln!(csv, "{}", row.join(",")).unwrap();
+    }
+    csv
+}
+
+pub fn front_to_json(front: &[ParetoRecord]) -> String {
+    let json_array = |values: &[f64]| {
+        let values: Vec<String> = values.iter().map(|value| json_number(*value)).collect();
+        format!("[{}]", values.join(","))
+    };
+
+    let objects: Vec<String> = front
+        .iter()
+        .map(|record| {
+            format!("{{\"location\":{},\"costs\":{}}}",
+                json_array(record.location()),
+                json_array(record.costs()),
+            )
+        })
+        .collect();
+    format!("[{}]", objects.join(","))
+}
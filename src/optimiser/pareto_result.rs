@@ -0,0 +1,109 @@
+use std::fmt;
+use std::time::Duration;
+
+use super::pareto_metrics::{
+    front_to_csv, front_to_json, hypervolume, inverted_generational_distance, spacing, spread,
+};
+use super::{ParetoRecord, PsoError, TerminationReason};
+
+#[derive(Clone, Debug)]
+pub struct ParetoSwarmResult {
+    pub name: String,
+    pub front: Vec<ParetoRecord>,
+    pub termination: TerminationReason,
+    pub itterations: usize,
+    pub evaluations: usize,
+    pub duration: Duration,
+}
+
+impl fmt::Display for ParetoSwarmResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} stopped after {} itterations ({}), {} evaluations in {:?}, {} non-dominated points",
+            self.name,
+            self.itterations,
+            self.termination,
+            self.evaluations,
+            self.duration,
+            self.front.len(),
+        )
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ParetoResult {
+    pub front: Vec<ParetoRecord>,
+    pub swarm_results: Vec<ParetoSwarmResult>,
+    pub evaluations: usize,
+    pub duration: Duration,
+}
+
+impl ParetoResult {
+    // front is the job's merged archive, ordered by the first objective
+    pub fn from_swarm_results(
+        swarm_results: Vec<ParetoSwarmResult>,
+        mut front: Vec<ParetoRecord>,
+        duration: Duration,
+    ) -> Result<Self, PsoError> {
+        if swarm_results.is_empty() {
+            return Err(PsoError::NoSwarmResults);
+        }
+
+        front.sort_by(|a, b| a.costs().partial_cmp(b.costs()).unwrap_or(std::cmp::Ordering::Equal));
+
+        Ok(ParetoResult {
+            front,
+            evaluations: swarm_results.iter().map(|sr| sr.evaluations).sum(),
+            swarm_results,
+            duration,
+        })
+    }
+
+    pub fn front(&self) -> &[ParetoRecord] {
+        &self.front
+    }
+
+    pub fn costs(&self) -> Vec<Vec<f64>> {
+        self.front.iter().map(|record| record.costs().to_vec()).collect()
+    }
+
+    pub fn locations(&self) -> Vec<Vec<f64>> {
+        self.front.iter().map(|record| record.location().to_vec()).collect()
+    }
+
+    // reference is a point every objective should stay below, e.g. the worst acceptable costs
+    pub fn hypervolume(&self, reference: &[f64]) -> Result<f64, PsoError> {
+        hypervolume(&self.front, reference)
+    }
+
+    // true_front holds the cost vectors of a known Pareto front, sampled densely enough to stand in for it
+    pub fn inverted_generational_distance(&self, true_front: &[Vec<f64>]) -> Result<f64, PsoError> {
+        inverted_generational_distance(&self.front, true_front)
+    }
+
+    pub fn spacing(&self) -> Result<f64, PsoError> {
+        spacing(&self.front)
+    }
+
+    pub fn spread(&self, true_front: &[Vec<f64>]) -> Result<f64, PsoError> {
+        spread(&self.front, true_front)
+    }
+
+    pub fn to_csv(&self) -> String {
+        front_to_csv(&self.front)
+    }
+
+    pub fn to_json(&self) -> String {
+        front_to_json(&self.front)
+    }
+}
+
+impl fmt::Display for ParetoResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} non-dominated points found by {} swarms, {} evaluations in {:?}",
+            self.front.len(),
+            self.swarm_results.len(),
+            self.evaluations,
+            self.duration,
+        )
+    }
+}
@@ -0,0 +1,153 @@
+use std::error::Error;
+use std::fmt;
+
+use super::{TerminationReason, VelocityUpdateRule};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PsoError {
+    NoSwarms,
+    TooManySwarms { max: usize, found: usize },
+    TooManyVariables { max: usize, found: usize },
+    NoVariables,
+    BoundsLengthMismatch { expected: usize, found: usize },
+    VelocitiesLengthMismatch { expected: usize, found: usize },
+    InvertedBounds { index: usize, lower: f64, upper: f64 },
+    NonPositiveVelocity { index: usize, value: f64 },
+    NonFiniteBounds { index: usize, lower: f64, upper: f64 },
+    NonFiniteVelocity { index: usize, value: f64 },
+    InvalidVariance(f64),
+    InvalidConstrictionPhi(f64),
+    InvalidContractionExpansion { max: f64, min: f64 },
+    EvolutionaryStateRequiresInertia(VelocityUpdateRule),
+    NonUniformStochasticity(VelocityUpdateRule),
+    MultiObjectiveUnsupported(String),
+    InvalidPenalty(f64),
+    InvalidPenaltyGrowth(f64),
+    EmptyParetoArchive,
+    ReferencePointMismatch { expected: usize, found: usize },
+    NoGridDivisions,
+    ObjectiveCountOutOfRange { min: usize, max: usize, found: usize },
+    ObjectiveCountMismatch { expected: usize, found: usize },
+    SwarmConfigCountMismatch { expected: usize, found: usize },
+    CollaborativeSingleSwarm,
+    MixedSwarmConfigs,
+    NoPendingAsk,
+    SwarmTerminated(TerminationReason),
+    AskTellEvolutionaryState,
+    CostCountMismatch { expected: usize, found: usize },
+    NoSwarmResults,
+    SwarmThreadFailed(String),
+}
+
+impl fmt::Display for PsoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NoSwarms => write!(f, "There must be at least one Swarm"),
+            Self::TooManySwarms { max, found } => {
+                write!(f, "Number of Swarms must not exceed {}! (found {})", max, found)
+            }
+            Self::TooManyVariables { max, found } => {
+                write!(f, "Number of variables must not exceed {}! (found {})", max, found)
+            }
+            Self::NoVariables => write!(f, "There must be at least one variable"),
+            Self::BoundsLengthMismatch { expected, found } => write!(f,
+                "Variable Bounds Vector must have one entry for each variable! (expected {}, found {})",
+                expected, found
+            ),
+            Self::VelocitiesLengthMismatch { expected, found } => write!(f,
+                "Max Velocities Vector must have one entry for each variable! (expected {}, found {})",
+                expected, found
+            ),
+            Self::InvertedBounds { index, lower, upper } => write!(f,
+                "All lower bounds must be less than upper bounds! (variable {}: {} to {})",
+                index, lower, upper
+            ),
+            Self::NonPositiveVelocity { index, value } => write!(f,
+                "Max values must be positive numbers! (variable {}: {})",
+                index, value
+            ),
+            Self::NonFiniteBounds { index, lower, upper } => write!(f,
+                "All bounds must be finite numbers! (variable {}: {} to {})",
+                index, lower, upper
+            ),
+            Self::NonFiniteVelocity { index, value } => write!(f,
+                "Max velocities must be finite numbers! (variable {}: {})",
+                index, value
+            ),
+            Self::InvalidVariance(variance) => write!(f,
+                "Parameter Distribution Variance must be between 0 and 1 (found {})",
+                variance
+            ),
+            Self::InvalidConstrictionPhi(phi) => write!(f,
+                "Constriction requires the attraction coefficients to sum to more than 4 (found {})",
+                phi
+            ),
+            Self::InvalidContractionExpansion { max, min } => write!(f,
+                "QPSO contraction-expansion coefficients must satisfy 0 < beta_min <= beta_max (found {} -> {})",
+                max, min
+            ),
+            Self::EvolutionaryStateRequiresInertia(rule) => write!(f,
+                "Evolutionary state estimation adapts the inertia velocity update, not the {} update",
+                rule
+            ),
+            Self::NonUniformStochasticity(rule) => write!(f,
+                "The {} velocity update draws its random factors from the uniform stochasticity schedule only!",
+                rule
+            ),
+            Self::MultiObjectiveUnsupported(feature) => write!(f,
+                "Multi-objective optimisation does not support {}!",
+                feature
+            ),
+            Self::InvalidPenalty(coefficient) => write!(f,
+                "Penalty coefficient must be positive and finite, found {}",
+                coefficient
+            ),
+            Self::InvalidPenaltyGrowth(growth) => write!(f,
+                "Adaptive penalty growth must be greater than 1 and finite, found {}",
+                growth
+            ),
+            Self::EmptyParetoArchive => write!(f, "The Pareto archive must hold at least one point"),
+            Self::ReferencePointMismatch { expected, found } => write!(f,
+                "Every point of a front and its reference must have one cost per objective! (expected {}, found {})",
+                expected, found
+            ),
+            Self::NoGridDivisions => write!(f, "Grid leader selection needs at least one division per objective"),
+            Self::ObjectiveCountOutOfRange { min, max, found } => write!(f,
+                "Multi-objective cost functions must return between {} and {} costs, found {}",
+                min, max, found
+            ),
+            Self::ObjectiveCountMismatch { expected, found } => write!(f,
+                "The cost function must return the same number of costs on every call! (expected {}, found {})",
+                expected, found
+            ),
+            Self::SwarmConfigCountMismatch { expected, found } => write!(f,
+                "One Swarm Configuration must be provided for each swarm-thread! (expected {}, found {})",
+                expected, found
+            ),
+            Self::CollaborativeSingleSwarm => write!(f,
+                "Single-Swarm Optimisation may not use a Collaborative Swarm Configuration!"
+            ),
+            Self::MixedSwarmConfigs => write!(f,
+                "All swarm configurations must have the same Collaborative or Independant status!"
+            ),
+            Self::NoPendingAsk => write!(f,
+                "Costs may only be told after positions have been asked for!"
+            ),
+            Self::SwarmTerminated(reason) => write!(f,
+                "The swarm has already terminated! ({})",
+                reason
+            ),
+            Self::AskTellEvolutionaryState => write!(f,
+                "Ask-Tell Swarms may not use evolutionary state estimation!"
+            ),
+            Self::CostCountMismatch { expected, found } => write!(f,
+                "One cost must be told for each asked position! (expected {}, found {})",
+                expected, found
+            ),
+            Self::NoSwarmResults => write!(f, "At least one swarm must report a result!"),
+            Self::SwarmThreadFailed(msg) => write!(f, "A swarm-thread failed during the job: {}", msg),
+        }
+    }
+}
+
+impl Error for PsoError {}
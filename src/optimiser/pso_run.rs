@@ -0,0 +1,136 @@
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use super::swarm::{SwarmColaborative, SwarmIndependant};
+use super::{Observer, OptimisationResult, Record, SwarmView, TerminationReason};
+
+enum RunSwarm {
+    Colaborative(SwarmColaborative),
+    Independant(SwarmIndependant),
+}
+
+impl RunSwarm {
+    fn termination(&self) -> Option<TerminationReason> {
+        match self {
+            Self::Colaborative(swarm) => swarm.termination(),
+            Self::Independant(swarm) => swarm.termination(),
+        }
+    }
+
+    fn view(&self) -> SwarmView<'_> {
+        match self {
+            Self::Colaborative(swarm) => swarm.view(),
+            Self::Independant(swarm) => swarm.view(),
+        }
+    }
+}
+
+// Single-threaded job driven one itteration at a time by the caller
+pub struct PsoRun<F> {
+    swarms: Vec<RunSwarm>,
+    global_record: Arc<RwLock<Record>>,
+    get_cost: Arc<F>,
+    observer: Arc<dyn Observer>,
+    is_reported: bool,
+    start: Instant,
+}
+
+impl<F> PsoRun<F>
+where
+    F: Fn(&[f64]) -> f64,
+{
+    pub fn new_collaborative(
+        swarms: Vec<SwarmColaborative>,
+        num_variables: usize,
+        get_cost: Arc<F>,
+        observer: Arc<dyn Observer>,
+    ) -> Self {
+        PsoRun {
+            swarms: swarms.into_iter().map(RunSwarm::Colaborative).collect(),
+            global_record: Arc::new(RwLock::new(Record::blank(num_variables))),
+            get_cost,
+            observer,
+            is_reported: false,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn new_independant(
+        swarms: Vec<SwarmIndependant>,
+        num_variables: usize,
+        get_cost: Arc<F>,
+        observer: Arc<dyn Observer>,
+    ) -> Self {
+        PsoRun {
+            swarms: swarms.into_iter().map(RunSwarm::Independant).collect(),
+            global_record: Arc::new(RwLock::new(Record::blank(num_variables))),
+            get_cost,
+            observer,
+            is_reported: false,
+            start: Instant::now(),
+        }
+    }
+
+    pub fn step(&mut self) -> Option<TerminationReason> {
+        for swarm in self.swarms.iter_mut() {
+            if swarm.termination().is_some() {
+                continue;
+            }
+
+            match swarm {
+                RunSwarm::Colaborative(swarm) => swarm.step(&self.get_cost, &self.global_record),
+                RunSwarm::Independant(swarm) => swarm.step(&self.get_cost),
+            }
+        }
+
+        let termination = self.termination();
+        if termination.is_some() && !self.is_reported {
+            self.observer.on_finish(&self.result());
+            self.is_reported = true;
+        }
+
+        termination
+    }
+
+    pub fn termination(&self) -> Option<TerminationReason> {
+        match self.is_finished() {
+            true => Some(self.result().termination),
+            false => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.swarms.iter().all(|swarm| swarm.termination().is_some())
+    }
+
+    pub fn swarms(&self) -> Vec<SwarmView<'_>> {
+        self.swarms.iter().map(|swarm| swarm.view()).collect()
+    }
+
+    pub fn global_record(&self) -> Record {
+        let mut record = self.global_record.read().unwrap().clone();
+        for swarm in self.swarms.iter() {
+            record.blind_accumulate(swarm.view().tribal_record());
+        }
+        record
+    }
+
+    // swarms that have not met a stopping criterion yet are reported as Stopped
+    pub fn result(&self) -> OptimisationResult {
+        let swarm_results = self
+            .swarms
+            .iter()
+            .map(|swarm| {
+                let termination = swarm.termination().unwrap_or(TerminationReason::Stopped);
+                match swarm {
+                    RunSwarm::Colaborative(swarm) => swarm.result(termination, self.start),
+                    RunSwarm::Independant(swarm) => swarm.result(termination, self.start),
+                }
+            })
+            .collect();
+
+        // a run is only ever started with at least one swarm
+        OptimisationResult::from_swarm_results(swarm_results, self.start.elapsed())
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+}
@@ -1,216 +1,532 @@
+mod ask_tell;
+mod comprehensive_learning;
+mod constraints;
+mod evolutionary_state;
+mod inertia;
+mod pareto_archive;
 mod particle;
+mod stochasticity;
 mod swarm_config;
 mod swarm_elements;
-
-pub use swarm_config::{ParamDist, SwarmConfig, SwarmConfigDistribution};
-use swarm_elements::SpaceRanger;
-pub use swarm_elements::{Bound, Record};
-
-use super::JobConfig;
+mod swarm_pareto;
+mod swarm_view;
+mod topology;
+
+pub use ask_tell::AskTellSwarm;
+use comprehensive_learning::Exemplars;
+pub use constraints::{ConstraintHandler, ConstraintHandling, Constraints};
+pub use evolutionary_state::EvolutionaryState;
+use evolutionary_state::{elitist_perturbation, StateEstimator};
+use inertia::InertiaWeight;
+pub use inertia::InertiaSchedule;
+pub use pareto_archive::{LeaderSelection, ParetoArchive, ParetoRecord};
+pub use stochasticity::StochasticitySchedule;
+pub use swarm_config::{FipsWeighting, ParamDist, SwarmConfig, SwarmConfigDistribution, VelocityUpdateRule};
+use swarm_elements::{SpaceRanger, StallMonitor};
+pub use swarm_elements::{Bound, BoundaryHandling, EvaluationBudget, Record};
+pub use swarm_pareto::SwarmPareto;
+pub use swarm_view::{ParticleView, SwarmView};
+use topology::Neighbourhoods;
+pub use topology::Topology;
+
+use super::{
+    HistoryEntry, JobConfig, Observer, PSOConfig, ParetoSwarmResult, PsoError, SwarmHistory, SwarmResult,
+    TerminationReason,
+};
 use particle::Particle;
 use rand::prelude::*;
+use rand::rngs::StdRng;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+fn initialise_particles(job_config: &JobConfig, num_particles: usize, rng: &mut StdRng) -> Vec<Particle> {
+    (0..num_particles)
+        .map(|_| {
+            let mut pos = job_config.pos_bounds.sample_vec(rng);
+            let mut vel = job_config.vel_bounds.sample_vec(rng);
+
+            Particle::new(&mut pos, &mut vel, job_config.num_variables)
+        })
+        .collect()
+}
+
+// mean euclidean distance of the particles from their centroid
+fn diversity(particles: &[Particle]) -> f64 {
+    if particles.is_empty() {
+        return 0.0;
+    }
+
+    let num_particles = particles.len() as f64;
+    let mut centroid = vec![0.0; particles[0].position().len()];
+    for p in particles.iter() {
+        for (c, x) in centroid.iter_mut().zip(p.position()) {
+            *c += x / num_particles;
+        }
+    }
+
+    particles
+        .iter()
+        .map(|p| {
+            p.position()
+                .iter()
+                .zip(centroid.iter())
+                .map(|(x, c)| (x - c).powi(2))
+                .sum::<f64>()
+                .sqrt()
+        })
+        .sum::<f64>()
+        / num_particles
+}
+
+// constriction scales the whole velocity update by chi, which is the inertia form with w = chi
+// and every attraction coefficient multiplied by chi
+fn velocity_coeffs(config: &SwarmConfig) -> ([f64; 4], InertiaSchedule) {
+    let [l, t, g, w] = config.motion_coeffs();
+    match config.constriction_factor() {
+        Some(chi) => ([chi * l, chi * t, chi * g, chi], InertiaSchedule::Constant),
+        None => ([l, t, g, w], config.inertia_schedule()),
+    }
+}
 
-fn stochasticity(iteration: usize, max_itterations: usize) -> f64 {
-    let progress = (iteration as f64) / (max_itterations as f64);
-    assert!(progress >= 0.0 && progress <= 1.0);
+// the particles' personal bests, gathered only for the rules that learn from other particles' bests
+fn personal_bests(particles: &[Particle], velocity_update_rule: VelocityUpdateRule) -> Vec<Record> {
+    match velocity_update_rule {
+        VelocityUpdateRule::Fips(_) | VelocityUpdateRule::Clpso { .. } | VelocityUpdateRule::Qpso { .. } => {
+            particles.iter().map(|p| p.get_record()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
 
-    let arctan = 2.0 * (1.0 - (std::f64::consts::PI * (progress - 0.5)).atan()) + 0.25;
-    let guass = (-1.0 * ((progress - std::f64::consts::FRAC_1_SQRT_2) / 0.025).powi(2)).exp();
+// mean position of the particles' personal bests, ignoring those not yet set
+fn mean_best(personal_bests: &[Record]) -> Vec<f64> {
+    let set: Vec<&Record> = personal_bests.iter().filter(|record| !record.is_blank()).collect();
+    let num_vars = personal_bests.first().map_or(0, |record| record.get_location().len());
+
+    (0..num_vars)
+        .map(|pi| set.iter().map(|record| record.pos(pi)).sum::<f64>() / set.len().max(1) as f64)
+        .collect()
+}
 
-    arctan + guass
+// QPSO contraction-expansion coefficient, falling linearly over max_itterations
+fn contraction_expansion(beta_max: f64, beta_min: f64, itteration: usize, max_itterations: usize) -> f64 {
+    let progress = match max_itterations {
+        0 => 1.0,
+        _ => (itteration as f64 / max_itterations as f64).min(1.0),
+    };
+    beta_max - (beta_max - beta_min) * progress
+}
+
+// mean cost of the particles actually evaluated, leaving out those skipped outside the search space
+fn mean_cost(particles: &[Particle], costs: &[f64], space_ranger: &SpaceRanger) -> Option<f64> {
+    let evaluated: Vec<f64> = particles
+        .iter()
+        .zip(costs.iter())
+        .filter(|(p, _)| !space_ranger.skips(p.position()))
+        .map(|(_, cost)| *cost)
+        .collect();
+
+    match evaluated.is_empty() {
+        true => None,
+        false => Some(evaluated.iter().sum::<f64>() / (evaluated.len() as f64)),
+    }
 }
 
 pub struct SwarmColaborative {
     name: String,
     motion_coeffs: [f64; 4],
-    _igf: f64,
-    wbf: f64,
+    inertia: InertiaWeight,
+    stochasticity_schedule: StochasticitySchedule,
+    velocity_update_rule: VelocityUpdateRule,
+    neighbourhoods: Neighbourhoods,
+    exemplars: Exemplars,
+    state_estimator: Option<StateEstimator>,
+    constraint_handler: ConstraintHandler,
     tgse: usize,
-    uce: Option<usize>,
+    observer: Arc<dyn Observer>,
+    budget: EvaluationBudget,
+    job_config: JobConfig,
+    space_ranger: SpaceRanger,
+    rng: StdRng,
     particles: Vec<Particle>,
-    num_particles: usize,
+    itteration: usize,
+    evaluations: usize,
+    no_new_record_count: i128,
+    stall_monitor: StallMonitor,
+    try_globalise_agian: bool,
     tribal_record: Record,
     global_record: Record,
+    history: Option<SwarmHistory>,
 }
 
 impl SwarmColaborative {
     pub fn new(
-        name: String,
+        index: usize,
         config: &SwarmConfig,
-        update_console_every: Option<usize>,
-        num_particles: usize,
+        job_config: JobConfig,
+        pso_config: &PSOConfig,
+        budget: EvaluationBudget,
+        constraint_handler: ConstraintHandler,
     ) -> Self {
+        let mut rng = seeded_rng(pso_config.stream_seed(index + 1));
+        let (mut motion_coeffs, inertia_schedule) = velocity_coeffs(config);
+        let mut inertia = InertiaWeight::new(
+            inertia_schedule,
+            motion_coeffs[3],
+            config.inertial_growth_factor(),
+            &mut rng,
+        );
+        motion_coeffs[3] = inertia.initial(motion_coeffs[3], job_config.max_itterations, &mut rng);
+        let neighbourhoods = Neighbourhoods::new(config.topology(), pso_config.nppt, &mut rng);
+        let particles = initialise_particles(&job_config, pso_config.nppt, &mut rng);
+        let exemplars = Exemplars::new(config.velocity_update_rule(), &particles, job_config.num_variables, &mut rng);
+
         SwarmColaborative {
-            name: name,
-            motion_coeffs: config.motion_coeffs(),
-            _igf: config.inertial_growth_factor(),
-            wbf: config.wall_bounce_factor(),
+            name: format!("Swarm_{}", index),
+            motion_coeffs,
+            inertia,
+            stochasticity_schedule: config.stochasticity_schedule(),
+            velocity_update_rule: config.velocity_update_rule(),
+            neighbourhoods,
+            exemplars,
+            state_estimator: match config.evolutionary_state_estimation() {
+                true => Some(StateEstimator::new()),
+                false => None,
+            },
+            constraint_handler,
             tgse: config.tribal_global_share_every(),
-            uce: update_console_every,
-            particles: Vec::with_capacity(num_particles),
-            num_particles: num_particles,
-            tribal_record: Record::blank(1),
-            global_record: Record::blank(1),
+            observer: pso_config.observer.clone(),
+            budget,
+            space_ranger: SpaceRanger::new(
+                job_config.num_variables,
+                &job_config.pos_bounds,
+                &job_config.vel_bounds,
+                config.wall_bounce_factor(),
+                job_config.boundary_handling,
+            ),
+            particles,
+            rng,
+            itteration: 0,
+            evaluations: 0,
+            no_new_record_count: 4,
+            stall_monitor: StallMonitor::new(),
+            try_globalise_agian: false,
+            tribal_record: Record::blank(job_config.num_variables),
+            global_record: Record::blank(job_config.num_variables),
+            history: match pso_config.record_history {
+                true => Some(SwarmHistory::new()),
+                false => None,
+            },
+            job_config,
         }
     }
 
-    pub fn run<F>(
-        &mut self,
-        job_config: JobConfig,
-        get_cost: Arc<F>,
-        global_record_lock: &mut Arc<RwLock<Record>>,
-    ) where
+    pub fn run<F>(&mut self, get_cost: Arc<F>, global_record_lock: Arc<RwLock<Record>>) -> SwarmResult
+    where
         F: Fn(&[f64]) -> f64,
     {
-        let mut rng = thread_rng();
+        let start = Instant::now();
 
-        self.tribal_record = Record::blank(job_config.num_variables);
-        self.global_record = Record::blank(job_config.num_variables);
-
-        let mut no_new_record_count = 4;
-        let mut try_globalise_agian = false;
-
-        self.initialise_particles(
-            job_config.num_variables,
-            &job_config.pos_bounds,
-            &job_config.vel_bounds,
-            &mut rng,
-        );
+        loop {
+            if let Some(termination) = self.termination() {
+                if termination == TerminationReason::ExitCost {
+                    self.try_globalise_agian = true;
+                    self.globalise(&global_record_lock);
+                }
+                return self.result(termination, start);
+            }
 
-        let space_ranger = SpaceRanger::new(
-            job_config.num_variables,
-            &job_config.pos_bounds,
-            &job_config.vel_bounds,
-            self.wbf,
-        );
+            self.step(&get_cost, &global_record_lock);
+        }
+    }
 
-        for i in 0..job_config.max_itterations {
-            let stoch = stochasticity(i, job_config.max_itterations);
+    pub fn step<F>(&mut self, get_cost: &Arc<F>, global_record_lock: &Arc<RwLock<Record>>)
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let stoch = self.stochasticity();
+
+        self.move_particles(stoch);
+
+        let costs: Vec<f64> = self
+            .particles
+            .iter()
+            .map_while(|p| match self.space_ranger.skips(p.position()) {
+                true => Some(f64::INFINITY),
+                false => p.test_cost(get_cost, &self.budget),
+            })
+            .collect();
+        self.record_costs(&costs);
+        self.elitist_learning(get_cost);
+
+        self.globalise(global_record_lock);
+
+        self.itteration += 1;
+
+        if let Some(history) = self.history.as_mut() {
+            history.push(HistoryEntry {
+                itteration: self.itteration,
+                tribal_best: self.tribal_record.get_cost(),
+                global_best: self.global_record.get_cost(),
+                mean_cost: mean_cost(&self.particles, &costs, &self.space_ranger),
+                diversity: diversity(&self.particles),
+                inertia: self.motion_coeffs[3],
+            });
+        }
 
-            self.update_particles(
-                &space_ranger,
-                &get_cost,
-                &mut no_new_record_count,
-                &mut rng,
-                stoch,
-            );
+        self.observer.on_iteration(&self.view());
+    }
 
-            self.globalise(i, global_record_lock, &mut try_globalise_agian);
+    pub fn termination(&self) -> Option<TerminationReason> {
+        if self.global_record.is_feasible() && self.global_record.get_cost() < self.job_config.exit_cost {
+            Some(TerminationReason::ExitCost)
+        } else if self.itteration >= self.job_config.max_itterations {
+            Some(TerminationReason::MaxItterations)
+        } else if self.budget.is_exhausted() {
+            Some(TerminationReason::EvaluationBudget)
+        } else if self.is_stalled() {
+            Some(TerminationReason::Stalled)
+        } else if self.is_collapsed() {
+            Some(TerminationReason::DiversityCollapsed)
+        } else {
+            self.job_config.interruption()
+        }
+    }
 
-            self.update_console(i, &no_new_record_count);
+    fn is_stalled(&self) -> bool {
+        match self.job_config.stall_itterations {
+            Some(stall_itterations) => self.stall_monitor.count() >= stall_itterations,
+            None => false,
+        }
+    }
 
-            if self.global_record.get_cost() < job_config.exit_cost {
-                self.globalise(i, global_record_lock, &mut true);
-                return;
-            }
+    fn is_collapsed(&self) -> bool {
+        match self.job_config.min_diversity {
+            Some(min_diversity) => diversity(&self.particles) < min_diversity,
+            None => false,
         }
     }
 
-    fn initialise_particles(
-        &mut self,
-        num_variables: usize,
-        pos_bounds: &Bound,
-        vel_bounds: &Bound,
-        rng: &mut ThreadRng,
-    ) {
-        for _ in 0..self.num_particles {
-            let mut pos = pos_bounds.sample_vec(rng);
-            let mut vel = vel_bounds.sample_vec(rng);
+    pub fn stochasticity(&self) -> [f64; 2] {
+        self.stochasticity_schedule.range(self.itteration, self.job_config.max_itterations)
+    }
 
-            self.particles
-                .push(Particle::new(&mut pos, &mut vel, num_variables));
+    pub fn view(&self) -> SwarmView<'_> {
+        SwarmView {
+            name: &self.name,
+            particles: &self.particles,
+            tribal_record: &self.tribal_record,
+            global_record: &self.global_record,
+            is_collaborative: true,
+            itteration: self.itteration,
+            stall_count: self.no_new_record_count,
+            motion_coeffs: self.motion_coeffs,
+            stochasticity: self.stochasticity(),
+            evolutionary_state: self.state_estimator.as_ref().map(|state_estimator| state_estimator.state()),
         }
     }
 
-    fn update_particles<F>(
-        &mut self,
-        space_ranger: &SpaceRanger,
-        get_cost: &Arc<F>,
-        no_new_record_count: &mut i128,
-        rng: &mut ThreadRng,
-        stoch: f64,
-    ) where
-        F: Fn(&[f64]) -> f64,
-    {
-        let mut best_cost = self.tribal_record.get_cost();
-        let mut best_cost_index = 0;
-
+    fn move_particles(&mut self, stoch: [f64; 2]) {
         let tribal_best_pos = self.tribal_record.get_location();
         let global_best_pos = self.global_record.get_location();
+        let personal_bests = personal_bests(&self.particles, self.velocity_update_rule);
+        let mean_best = match self.velocity_update_rule {
+            VelocityUpdateRule::Qpso { .. } => mean_best(&personal_bests),
+            _ => Vec::new(),
+        };
+        let num_particles = self.particles.len();
 
         for (i, p) in self.particles.iter_mut().enumerate() {
-            p.update_colab(
-                &self.motion_coeffs,
-                rng,
-                stoch,
-                &tribal_best_pos,
-                &global_best_pos,
-            );
-
-            space_ranger.constrain(p.pos_vel());
-
-            if let Some(new_cost) = p.test_cost(get_cost) {
-                if new_cost < best_cost {
-                    best_cost = new_cost;
-                    best_cost_index = i;
+            let (local_best_pos, is_global_better) = match self.neighbourhoods.is_global() {
+                true => (tribal_best_pos.clone(), self.global_record < self.tribal_record),
+                false => (p.neighbourhood_best().get_location(), self.global_record < *p.neighbourhood_best()),
+            };
+            // the velocity-free rules sample around a single social best: the global record once another swarm
+            // has shared one better than the local best
+            let social_best_pos = match is_global_better {
+                true => &global_best_pos,
+                false => &local_best_pos,
+            };
+
+            match self.velocity_update_rule {
+                VelocityUpdateRule::Spso2011 => p.update_spso(
+                    &self.motion_coeffs,
+                    &mut self.rng,
+                    &[&local_best_pos, &global_best_pos],
+                ),
+                VelocityUpdateRule::Inertia | VelocityUpdateRule::Constriction => p.update_colab(
+                    &self.motion_coeffs,
+                    &mut self.rng,
+                    stoch,
+                    &local_best_pos,
+                    &global_best_pos,
+                ),
+                VelocityUpdateRule::Fips(weighting) => {
+                    // the global collective informs every particle of a collaborative swarm
+                    let mut informants: Vec<&Record> = self
+                        .neighbourhoods
+                        .informants(i, num_particles)
+                        .into_iter()
+                        .map(|j| &personal_bests[j])
+                        .collect();
+                    informants.push(&self.global_record);
+
+                    p.update_fips(&self.motion_coeffs, &mut self.rng, stoch, &informants, weighting)
+                }
+                VelocityUpdateRule::Clpso { .. } => p.update_clpso(
+                    &self.motion_coeffs,
+                    &mut self.rng,
+                    stoch,
+                    &self.exemplars.position(i, &personal_bests),
+                    Some(&global_best_pos[..]),
+                ),
+                VelocityUpdateRule::BareBones => p.update_bare_bones(&mut self.rng, social_best_pos),
+                VelocityUpdateRule::Qpso { beta_max, beta_min } => {
+                    let beta =
+                        contraction_expansion(beta_max, beta_min, self.itteration, self.job_config.max_itterations);
+
+                    p.update_qpso(&mut self.rng, beta, &mean_best, social_best_pos)
                 }
             }
+
+            self.space_ranger.constrain(p.pos_vel(), &mut self.rng);
         }
+    }
 
-        let top_record = self.particles[best_cost_index].get_record();
+    fn record_costs(&mut self, costs: &[f64]) {
+        let mut top_record = self.tribal_record.clone();
+        let mut improved = vec![false; self.particles.len()];
 
-        if top_record < self.tribal_record {
+        for (i, (p, cost)) in self.particles.iter_mut().zip(costs).enumerate() {
+            if self.space_ranger.skips(p.position()) {
+                continue;
+            }
+
+            self.evaluations += 1;
+            let record = self.constraint_handler.score(*cost, p.position());
+            if p.tell_record(record) {
+                improved[i] = true;
+                if *p.record() < top_record {
+                    top_record = p.get_record();
+                }
+            }
+        }
+
+        let is_improved = top_record < self.tribal_record;
+
+        if is_improved {
             self.tribal_record = top_record;
-            *no_new_record_count -= 1;
-
-            if *no_new_record_count < 0 {
-                *no_new_record_count = 0;
-            } else if *no_new_record_count < 2 {
-                //self.motion_coeffs[3] *= self._igf;
-            } else if *no_new_record_count > 7 {
-                //self.motion_coeffs[3] /= self._igf;
+            self.observer.on_new_tribal_record(&self.name, self.itteration, &self.tribal_record);
+            self.no_new_record_count -= 1;
+
+            if self.no_new_record_count < 0 {
+                self.no_new_record_count = 0;
             }
         } else {
-            *no_new_record_count += 1;
+            self.no_new_record_count += 1;
+        }
+
+        if let Some(coefficient) = self.constraint_handler.adapt(&self.tribal_record) {
+            self.tribal_record.penalise(coefficient);
+            self.global_record.penalise(coefficient);
+            for p in self.particles.iter_mut() {
+                p.penalise(coefficient);
+            }
+        }
+
+        self.neighbourhoods.share(&mut self.particles, is_improved, &mut self.rng);
+        self.exemplars.refresh(&self.particles, &improved, &mut self.rng);
+
+        self.motion_coeffs[3] = self.inertia.next(
+            self.motion_coeffs[3],
+            self.itteration + 1,
+            self.job_config.max_itterations,
+            self.no_new_record_count,
+            &mut self.rng,
+        );
+        if let Some(state_estimator) = self.state_estimator.as_mut() {
+            state_estimator.adapt(&self.particles, &mut self.motion_coeffs, &mut self.rng);
+        }
+
+        self.stall_monitor.update(&self.tribal_record, self.job_config.stall_tolerance);
+    }
+
+    // APSO elitist learning: a converging swarm tries a perturbed copy of its best, which either becomes the new
+    // tribal record or replaces the particle with the worst personal best
+    fn elitist_learning<F>(&mut self, get_cost: &Arc<F>)
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let is_converging = match self.state_estimator.as_ref() {
+            Some(state_estimator) => state_estimator.state() == EvolutionaryState::Convergence,
+            None => false,
+        };
+        if !is_converging || self.tribal_record.is_blank() || !self.budget.try_take() {
+            return;
+        }
+
+        let position = elitist_perturbation(
+            &self.tribal_record,
+            &self.job_config.pos_bounds.range_vec(),
+            self.itteration,
+            self.job_config.max_itterations,
+            &mut self.rng,
+        );
+        let cost = get_cost(&position);
+        self.evaluations += 1;
+
+        let record = self.constraint_handler.score(cost, &position).with_location(&position);
+
+        if self.tribal_record.loses_to(&record) {
+            self.tribal_record = record;
+            self.observer.on_new_tribal_record(&self.name, self.itteration, &self.tribal_record);
+        } else if let Some(worst) = self
+            .particles
+            .iter_mut()
+            .max_by(|a, b| a.record().partial_cmp(b.record()).unwrap_or(std::cmp::Ordering::Equal))
+        {
+            worst.relocate(&position);
+            worst.tell_record(record);
         }
     }
 
-    fn globalise(
-        &mut self,
-        itteration: usize,
-        global_record_lock: &mut Arc<RwLock<Record>>,
-        try_agian: &mut bool,
-    ) {
-        if (itteration + 1) % self.tgse == 0 || *try_agian {
+    fn globalise(&mut self, global_record_lock: &Arc<RwLock<Record>>) {
+        if (self.itteration + 1) % self.tgse == 0 || self.try_globalise_agian {
             match global_record_lock.try_write() {
                 Ok(mut gr_write_ref) => {
+                    // the shared record may have been scored by a swarm that had not seen the job's latest penalty
+                    self.constraint_handler.penalise(&mut gr_write_ref);
                     if self.tribal_record < *gr_write_ref {
                         self.global_record = self.tribal_record.clone();
                         *gr_write_ref = self.global_record.clone();
                     } else {
                         self.global_record = (*gr_write_ref).clone();
                     }
-                    *try_agian = false;
+                    self.try_globalise_agian = false;
+                    self.observer.on_globalise(&self.name, self.itteration, &self.global_record);
                 }
-                Err(_) => *try_agian = true,
+                Err(_) => self.try_globalise_agian = true,
             }
         }
     }
 
-    fn update_console(&self, itteration: usize, no_new_record_count: &i128) {
-        if let Some(update_period) = self.uce {
-            if (itteration + 1) % update_period == 0 {
-                println!(
-                    "{} iter {}: \t c = {}, w = {} \n \t Tribal {} \n \t Global {}",
-                    self.name,
-                    itteration + 1,
-                    *no_new_record_count,
-                    self.motion_coeffs[3],
-                    self.tribal_record,
-                    self.global_record
-                );
-            }
+    pub fn result(&self, termination: TerminationReason, start: Instant) -> SwarmResult {
+        SwarmResult {
+            name: self.name.clone(),
+            record: self.constraint_handler.rescored(&self.tribal_record),
+            termination,
+            itterations: self.itteration,
+            evaluations: self.evaluations,
+            inertia: self.motion_coeffs[3],
+            duration: start.elapsed(),
+            history: self.history.clone(),
         }
     }
 }
@@ -218,152 +534,340 @@ impl SwarmColaborative {
 pub struct SwarmIndependant {
     name: String,
     motion_coeffs: [f64; 4],
-    _igf: f64,
-    wbf: f64,
-    uce: Option<usize>,
+    inertia: InertiaWeight,
+    stochasticity_schedule: StochasticitySchedule,
+    velocity_update_rule: VelocityUpdateRule,
+    neighbourhoods: Neighbourhoods,
+    exemplars: Exemplars,
+    state_estimator: Option<StateEstimator>,
+    constraint_handler: ConstraintHandler,
+    observer: Arc<dyn Observer>,
+    budget: EvaluationBudget,
+    job_config: JobConfig,
+    space_ranger: SpaceRanger,
+    rng: StdRng,
     particles: Vec<Particle>,
-    num_particles: usize,
+    itteration: usize,
+    evaluations: usize,
+    no_new_record_count: i128,
+    stall_monitor: StallMonitor,
     record: Record,
+    history: Option<SwarmHistory>,
 }
 
 impl SwarmIndependant {
     pub fn new(
-        name: String,
+        index: usize,
         config: &SwarmConfig,
-        update_console_every: Option<usize>,
-        num_particles: usize,
+        job_config: JobConfig,
+        pso_config: &PSOConfig,
+        budget: EvaluationBudget,
+        constraint_handler: ConstraintHandler,
     ) -> Self {
+        let mut rng = seeded_rng(pso_config.stream_seed(index + 1));
+        let (mut motion_coeffs, inertia_schedule) = velocity_coeffs(config);
+        let mut inertia = InertiaWeight::new(
+            inertia_schedule,
+            motion_coeffs[3],
+            config.inertial_growth_factor(),
+            &mut rng,
+        );
+        motion_coeffs[3] = inertia.initial(motion_coeffs[3], job_config.max_itterations, &mut rng);
+        let neighbourhoods = Neighbourhoods::new(config.topology(), pso_config.nppt, &mut rng);
+        let particles = initialise_particles(&job_config, pso_config.nppt, &mut rng);
+        let exemplars = Exemplars::new(config.velocity_update_rule(), &particles, job_config.num_variables, &mut rng);
+
         SwarmIndependant {
-            name: name,
-            motion_coeffs: config.motion_coeffs(),
-            _igf: config.inertial_growth_factor(),
-            wbf: config.wall_bounce_factor(),
-            uce: update_console_every,
-            particles: Vec::with_capacity(num_particles),
-            num_particles: num_particles,
-            record: Record::blank(1),
+            name: format!("Swarm_{}", index),
+            motion_coeffs,
+            inertia,
+            stochasticity_schedule: config.stochasticity_schedule(),
+            velocity_update_rule: config.velocity_update_rule(),
+            neighbourhoods,
+            exemplars,
+            state_estimator: match config.evolutionary_state_estimation() {
+                true => Some(StateEstimator::new()),
+                false => None,
+            },
+            constraint_handler,
+            observer: pso_config.observer.clone(),
+            budget,
+            space_ranger: SpaceRanger::new(
+                job_config.num_variables,
+                &job_config.pos_bounds,
+                &job_config.vel_bounds,
+                config.wall_bounce_factor(),
+                job_config.boundary_handling,
+            ),
+            particles,
+            rng,
+            itteration: 0,
+            evaluations: 0,
+            no_new_record_count: 4,
+            stall_monitor: StallMonitor::new(),
+            record: Record::blank(job_config.num_variables),
+            history: match pso_config.record_history {
+                true => Some(SwarmHistory::new()),
+                false => None,
+            },
+            job_config,
         }
     }
 
-    pub fn run<F>(&mut self, job_config: JobConfig, get_cost: Arc<F>) -> Record
+    pub fn run<F>(&mut self, get_cost: Arc<F>) -> SwarmResult
     where
         F: Fn(&[f64]) -> f64,
     {
-        let mut rng = thread_rng();
+        let start = Instant::now();
 
-        self.record = Record::blank(job_config.num_variables);
-
-        let mut no_new_record_count = 4;
-
-        self.initialise_particles(
-            job_config.num_variables,
-            &job_config.pos_bounds,
-            &job_config.vel_bounds,
-            &mut rng,
-        );
+        loop {
+            if let Some(termination) = self.termination() {
+                return self.result(termination, start);
+            }
 
-        let space_ranger = SpaceRanger::new(
-            job_config.num_variables,
-            &job_config.pos_bounds,
-            &job_config.vel_bounds,
-            self.wbf,
-        );
+            self.step(&get_cost);
+        }
+    }
 
-        for i in 0..job_config.max_itterations {
-            let stoch = stochasticity(i, job_config.max_itterations);
+    pub fn step<F>(&mut self, get_cost: &Arc<F>)
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let stoch = self.stochasticity();
+
+        self.move_particles(stoch);
+
+        let costs: Vec<f64> = self
+            .particles
+            .iter()
+            .map_while(|p| match self.space_ranger.skips(p.position()) {
+                true => Some(f64::INFINITY),
+                false => p.test_cost(get_cost, &self.budget),
+            })
+            .collect();
+        self.record_costs(&costs);
+        self.elitist_learning(get_cost);
+
+        self.itteration += 1;
+
+        if let Some(history) = self.history.as_mut() {
+            history.push(HistoryEntry {
+                itteration: self.itteration,
+                tribal_best: self.record.get_cost(),
+                global_best: self.record.get_cost(),
+                mean_cost: mean_cost(&self.particles, &costs, &self.space_ranger),
+                diversity: diversity(&self.particles),
+                inertia: self.motion_coeffs[3],
+            });
+        }
 
-            self.update_particles(
-                &space_ranger,
-                &get_cost,
-                &mut no_new_record_count,
-                &mut rng,
-                stoch,
-            );
+        self.observer.on_iteration(&self.view());
+    }
 
-            self.update_console(i, &no_new_record_count);
+    pub fn termination(&self) -> Option<TerminationReason> {
+        if self.record.is_feasible() && self.record.get_cost() < self.job_config.exit_cost {
+            Some(TerminationReason::ExitCost)
+        } else if self.itteration >= self.job_config.max_itterations {
+            Some(TerminationReason::MaxItterations)
+        } else if self.budget.is_exhausted() {
+            Some(TerminationReason::EvaluationBudget)
+        } else if self.is_stalled() {
+            Some(TerminationReason::Stalled)
+        } else if self.is_collapsed() {
+            Some(TerminationReason::DiversityCollapsed)
+        } else {
+            self.job_config.interruption()
+        }
+    }
 
-            if self.record.get_cost() < job_config.exit_cost {
-                return self.record.clone();
-            }
+    fn is_stalled(&self) -> bool {
+        match self.job_config.stall_itterations {
+            Some(stall_itterations) => self.stall_monitor.count() >= stall_itterations,
+            None => false,
         }
+    }
 
-        self.record.clone()
+    fn is_collapsed(&self) -> bool {
+        match self.job_config.min_diversity {
+            Some(min_diversity) => diversity(&self.particles) < min_diversity,
+            None => false,
+        }
     }
 
-    fn initialise_particles(
-        &mut self,
-        num_variables: usize,
-        pos_bounds: &Bound,
-        vel_bounds: &Bound,
-        rng: &mut ThreadRng,
-    ) {
-        for _ in 0..self.num_particles {
-            let mut pos = pos_bounds.sample_vec(rng);
-            let mut vel = vel_bounds.sample_vec(rng);
+    pub fn stochasticity(&self) -> [f64; 2] {
+        self.stochasticity_schedule.range(self.itteration, self.job_config.max_itterations)
+    }
 
-            self.particles
-                .push(Particle::new(&mut pos, &mut vel, num_variables));
+    pub fn view(&self) -> SwarmView<'_> {
+        SwarmView {
+            name: &self.name,
+            particles: &self.particles,
+            tribal_record: &self.record,
+            global_record: &self.record,
+            is_collaborative: false,
+            itteration: self.itteration,
+            stall_count: self.no_new_record_count,
+            motion_coeffs: self.motion_coeffs,
+            stochasticity: self.stochasticity(),
+            evolutionary_state: self.state_estimator.as_ref().map(|state_estimator| state_estimator.state()),
         }
     }
 
-    fn update_particles<F>(
-        &mut self,
-        space_ranger: &SpaceRanger,
-        get_cost: &Arc<F>,
-        no_new_record_count: &mut i128,
-        rng: &mut ThreadRng,
-        stoch: f64,
-    ) where
-        F: Fn(&[f64]) -> f64,
-    {
-        let mut best_cost = self.record.get_cost();
-        let mut best_cost_index = 0;
-
+    fn move_particles(&mut self, stoch: [f64; 2]) {
         let best_pos = self.record.get_location();
+        let personal_bests = personal_bests(&self.particles, self.velocity_update_rule);
+        let mean_best = match self.velocity_update_rule {
+            VelocityUpdateRule::Qpso { .. } => mean_best(&personal_bests),
+            _ => Vec::new(),
+        };
+        let num_particles = self.particles.len();
 
         for (i, p) in self.particles.iter_mut().enumerate() {
-            p.update_indep(&self.motion_coeffs, rng, stoch, &best_pos);
+            let local_best_pos = match self.neighbourhoods.is_global() {
+                true => best_pos.clone(),
+                false => p.neighbourhood_best().get_location(),
+            };
+
+            match self.velocity_update_rule {
+                VelocityUpdateRule::Spso2011 => p.update_spso(&self.motion_coeffs, &mut self.rng, &[&local_best_pos]),
+                VelocityUpdateRule::Inertia | VelocityUpdateRule::Constriction => {
+                    p.update_indep(&self.motion_coeffs, &mut self.rng, stoch, &local_best_pos)
+                }
+                VelocityUpdateRule::Fips(weighting) => {
+                    let informants: Vec<&Record> = self
+                        .neighbourhoods
+                        .informants(i, num_particles)
+                        .into_iter()
+                        .map(|j| &personal_bests[j])
+                        .collect();
+
+                    p.update_fips(&self.motion_coeffs, &mut self.rng, stoch, &informants, weighting)
+                }
+                VelocityUpdateRule::Clpso { .. } => p.update_clpso(
+                    &self.motion_coeffs,
+                    &mut self.rng,
+                    stoch,
+                    &self.exemplars.position(i, &personal_bests),
+                    None,
+                ),
+                VelocityUpdateRule::BareBones => p.update_bare_bones(&mut self.rng, &local_best_pos),
+                VelocityUpdateRule::Qpso { beta_max, beta_min } => {
+                    let beta =
+                        contraction_expansion(beta_max, beta_min, self.itteration, self.job_config.max_itterations);
+                    p.update_qpso(&mut self.rng, beta, &mean_best, &local_best_pos)
+                }
+            }
 
-            space_ranger.constrain(p.pos_vel());
+            self.space_ranger.constrain(p.pos_vel(), &mut self.rng);
+        }
+    }
 
-            if let Some(new_cost) = p.test_cost(get_cost) {
-                if new_cost < best_cost {
-                    best_cost = new_cost;
-                    best_cost_index = i;
+    fn record_costs(&mut self, costs: &[f64]) {
+        let mut top_record = self.record.clone();
+        let mut improved = vec![false; self.particles.len()];
+
+        for (i, (p, cost)) in self.particles.iter_mut().zip(costs).enumerate() {
+            if self.space_ranger.skips(p.position()) {
+                continue;
+            }
+
+            self.evaluations += 1;
+            let record = self.constraint_handler.score(*cost, p.position());
+            if p.tell_record(record) {
+                improved[i] = true;
+                if *p.record() < top_record {
+                    top_record = p.get_record();
                 }
             }
         }
 
-        let top_record = self.particles[best_cost_index].get_record();
+        let is_improved = top_record < self.record;
 
-        if top_record < self.record {
+        if is_improved {
             self.record = top_record;
-            *no_new_record_count -= 1;
-
-            if *no_new_record_count < 0 {
-                *no_new_record_count = 0;
-            } else if *no_new_record_count < 2 {
-                //self.motion_coeffs[3] *= self._igf;
-            } else if *no_new_record_count > 7 {
-                //self.motion_coeffs[3] /= self._igf;
+            self.observer.on_new_tribal_record(&self.name, self.itteration, &self.record);
+            self.no_new_record_count -= 1;
+
+            if self.no_new_record_count < 0 {
+                self.no_new_record_count = 0;
             }
         } else {
-            *no_new_record_count += 1;
+            self.no_new_record_count += 1;
         }
-    }
 
-    fn update_console(&self, itteration: usize, no_new_record_count: &i128) {
-        if let Some(update_period) = self.uce {
-            if (itteration + 1) % update_period == 0 {
-                println!(
-                    "{} iter {}: \t c = {}, w = {} \n \t {} ",
-                    self.name,
-                    itteration + 1,
-                    *no_new_record_count,
-                    self.motion_coeffs[3],
-                    self.record,
-                );
+        if let Some(coefficient) = self.constraint_handler.adapt(&self.record) {
+            self.record.penalise(coefficient);
+            for p in self.particles.iter_mut() {
+                p.penalise(coefficient);
             }
         }
+
+        self.neighbourhoods.share(&mut self.particles, is_improved, &mut self.rng);
+        self.exemplars.refresh(&self.particles, &improved, &mut self.rng);
+
+        self.motion_coeffs[3] = self.inertia.next(
+            self.motion_coeffs[3],
+            self.itteration + 1,
+            self.job_config.max_itterations,
+            self.no_new_record_count,
+            &mut self.rng,
+        );
+        if let Some(state_estimator) = self.state_estimator.as_mut() {
+            state_estimator.adapt(&self.particles, &mut self.motion_coeffs, &mut self.rng);
+        }
+
+        self.stall_monitor.update(&self.record, self.job_config.stall_tolerance);
     }
-}
\ No newline at end of file
+
+    // APSO elitist learning: a converging swarm tries a perturbed copy of its best, which either becomes the new
+    // record or replaces the particle with the worst personal best
+    fn elitist_learning<F>(&mut self, get_cost: &Arc<F>)
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let is_converging = match self.state_estimator.as_ref() {
+            Some(state_estimator) => state_estimator.state() == EvolutionaryState::Convergence,
+            None => false,
+        };
+        if !is_converging || self.record.is_blank() || !self.budget.try_take() {
+            return;
+        }
+
+        let position = elitist_perturbation(
+            &self.record,
+            &self.job_config.pos_bounds.range_vec(),
+            self.itteration,
+            self.job_config.max_itterations,
+            &mut self.rng,
+        );
+        let cost = get_cost(&position);
+        self.evaluations += 1;
+
+        let record = self.constraint_handler.score(cost, &position).with_location(&position);
+
+        if self.record.loses_to(&record) {
+            self.record = record;
+            self.observer.on_new_tribal_record(&self.name, self.itteration, &self.record);
+        } else if let Some(worst) = self
+            .particles
+            .iter_mut()
+            .max_by(|a, b| a.record().partial_cmp(b.record()).unwrap_or(std::cmp::Ordering::Equal))
+        {
+            worst.relocate(&position);
+            worst.tell_record(record);
+        }
+    }
+
+    pub fn result(&self, termination: TerminationReason, start: Instant) -> SwarmResult {
+        SwarmResult {
+            name: self.name.clone(),
+            record: self.constraint_handler.rescored(&self.record),
+            termination,
+            itterations: self.itteration,
+            evaluations: self.evaluations,
+            inertia: self.motion_coeffs[3],
+            duration: start.elapsed(),
+            history: self.history.clone(),
+        }
+    }
+}
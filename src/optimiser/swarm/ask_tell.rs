@@ -0,0 +1,94 @@
+use super::{ConstraintHandler, EvaluationBudget, JobConfig, PSOConfig, PsoError, Record, SwarmConfig, SwarmIndependant};
+
+// Step-wise independant swarm: the caller evaluates the positions handed out by ask()
+// and reports their costs back through tell(), which advances the swarm one itteration.
+// Unlike a full run, ask/tell never pushes history entries nor notifies the job's observer
+pub struct AskTellSwarm {
+    swarm: SwarmIndependant,
+    is_asked: bool,
+}
+
+impl AskTellSwarm {
+    pub fn new(
+        job_config: JobConfig,
+        swarm_config: &SwarmConfig,
+        num_particles: usize,
+        seed: Option<u64>,
+    ) -> Result<Self, PsoError> {
+        if swarm_config.is_collaborative() {
+            return Err(PsoError::CollaborativeSingleSwarm);
+        }
+        swarm_config.verify()?;
+        // APSO's elitist learning needs its own evaluations, which ask/tell has no place for
+        if swarm_config.evolutionary_state_estimation() {
+            return Err(PsoError::AskTellEvolutionaryState);
+        }
+        job_config.constraint_handling.verify()?;
+
+        let mut pso_config = PSOConfig::try_new(1, num_particles, 0, false)?;
+        pso_config.seed = seed;
+
+        let budget = EvaluationBudget::new(job_config.max_evaluations);
+        let constraint_handler = ConstraintHandler::new(&job_config.constraints, job_config.constraint_handling);
+        let swarm = SwarmIndependant::new(0, swarm_config, job_config, &pso_config, budget, constraint_handler);
+
+        Ok(AskTellSwarm { swarm, is_asked: false })
+    }
+
+    pub fn ask(&mut self) -> Result<Vec<Vec<f64>>, PsoError> {
+        if let Some(reason) = self.swarm.termination() {
+            return Err(PsoError::SwarmTerminated(reason));
+        }
+        if !self.is_asked {
+            let stoch = self.swarm.stochasticity();
+            self.swarm.move_particles(stoch);
+            self.is_asked = true;
+        }
+
+        Ok(self.swarm.particles.iter().map(|p| p.position().to_vec()).collect())
+    }
+
+    pub fn tell(&mut self, costs: &[f64]) -> Result<(), PsoError> {
+        if let Some(reason) = self.swarm.termination() {
+            return Err(PsoError::SwarmTerminated(reason));
+        }
+        if !self.is_asked {
+            return Err(PsoError::NoPendingAsk);
+        }
+        if costs.len() != self.swarm.particles.len() {
+            return Err(PsoError::CostCountMismatch {
+                expected: self.swarm.particles.len(),
+                found: costs.len(),
+            });
+        }
+
+        // costs beyond the evaluation budget, and of particles skipped by BoundaryHandling::Infinity, are ignored
+        let swarm = &self.swarm;
+        let allowed = swarm
+            .particles
+            .iter()
+            .take_while(|p| swarm.space_ranger.skips(p.position()) || swarm.budget.try_take())
+            .count();
+        self.swarm.record_costs(&costs[..allowed]);
+
+        self.swarm.itteration += 1;
+        self.is_asked = false;
+        Ok(())
+    }
+
+    pub fn best(&self) -> Record {
+        self.swarm.record.clone()
+    }
+
+    pub fn itteration(&self) -> usize {
+        self.swarm.itteration
+    }
+
+    pub fn evaluations(&self) -> usize {
+        self.swarm.evaluations
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.swarm.termination().is_some()
+    }
+}
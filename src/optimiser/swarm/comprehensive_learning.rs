@@ -0,0 +1,130 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use super::particle::Particle;
+use super::{Record, VelocityUpdateRule};
+
+const MIN_LEARNING_PROBABILITY: f64 = 0.05;
+const MAX_LEARNING_PROBABILITY: f64 = 0.5;
+
+// CLPSO exemplars: exemplars[i][d] is the particle whose personal best particle i learns dimension d from
+pub struct Exemplars {
+    refresh_gap: usize,
+    learning_probabilities: Vec<f64>,
+    exemplars: Vec<Vec<usize>>,
+    stale_counts: Vec<usize>,
+}
+
+impl Exemplars {
+    // empty unless the swarm uses the CLPSO rule, so other rules draw nothing from the rng
+    pub fn new(
+        velocity_update_rule: VelocityUpdateRule,
+        particles: &[Particle],
+        num_vars: usize,
+        rng: &mut StdRng,
+    ) -> Self {
+        let refresh_gap = match velocity_update_rule {
+            VelocityUpdateRule::Clpso { refresh_gap } => refresh_gap,
+            _ => {
+                return Exemplars {
+                    refresh_gap: 0,
+                    learning_probabilities: Vec::new(),
+                    exemplars: Vec::new(),
+                    stale_counts: Vec::new(),
+                }
+            }
+        };
+
+        let num_particles = particles.len();
+        let learning_probabilities = (0..num_particles)
+            .map(|i| learning_probability(i, num_particles))
+            .collect();
+
+        let mut exemplars = Exemplars {
+            refresh_gap,
+            learning_probabilities,
+            exemplars: vec![Vec::new(); num_particles],
+            stale_counts: vec![0; num_particles],
+        };
+        for i in 0..num_particles {
+            exemplars.assign(i, particles, num_vars, rng);
+        }
+        exemplars
+    }
+
+    // the position particle i learns from, built dimension by dimension from its exemplars' personal bests
+    pub fn position(&self, particle: usize, personal_bests: &[Record]) -> Vec<f64> {
+        self.exemplars[particle]
+            .iter()
+            .enumerate()
+            .map(|(d, &j)| personal_bests[j].pos(d))
+            .collect()
+    }
+
+    // particles whose personal best has not improved for more than refresh_gap itterations get new exemplars
+    pub fn refresh(&mut self, particles: &[Particle], improved: &[bool], rng: &mut StdRng) {
+        for i in 0..self.exemplars.len() {
+            match improved.get(i).copied().unwrap_or(false) {
+                true => self.stale_counts[i] = 0,
+                false => self.stale_counts[i] += 1,
+            }
+
+            if self.stale_counts[i] > self.refresh_gap {
+                let num_vars = self.exemplars[i].len();
+                self.assign(i, particles, num_vars, rng);
+                self.stale_counts[i] = 0;
+            }
+        }
+    }
+
+    fn assign(&mut self, particle: usize, particles: &[Particle], num_vars: usize, rng: &mut StdRng) {
+        let num_particles = particles.len();
+        let mut exemplar: Vec<usize> = (0..num_vars)
+            .map(|_| match rng.gen::<f64>() < self.learning_probabilities[particle] {
+                true => tournament(particle, particles, rng),
+                false => particle,
+            })
+            .collect();
+
+        // a particle learning only from itself is made to learn one random dimension from another
+        if num_particles > 1 && num_vars > 0 && exemplar.iter().all(|&j| j == particle) {
+            let d = rng.gen_range(0, num_vars);
+            exemplar[d] = tournament(particle, particles, rng);
+        }
+
+        self.exemplars[particle] = exemplar;
+    }
+}
+
+// learning probabilities rise from 0.05 to 0.5 across the swarm, so some particles mostly exploit their own best
+fn learning_probability(particle: usize, num_particles: usize) -> f64 {
+    if num_particles < 2 {
+        return MIN_LEARNING_PROBABILITY;
+    }
+
+    let progress = 10.0 * particle as f64 / (num_particles - 1) as f64;
+    MIN_LEARNING_PROBABILITY
+        + (MAX_LEARNING_PROBABILITY - MIN_LEARNING_PROBABILITY) * (progress.exp() - 1.0) / (10f64.exp() - 1.0)
+}
+
+// the better personal best of two particles other than the learner, drawn at random
+fn tournament(particle: usize, particles: &[Particle], rng: &mut StdRng) -> usize {
+    let num_particles = particles.len();
+    if num_particles < 2 {
+        return particle;
+    }
+
+    let mut draw = || {
+        let j = rng.gen_range(0, num_particles - 1);
+        match j >= particle {
+            true => j + 1,
+            false => j,
+        }
+    };
+    let (a, b) = (draw(), draw());
+
+    match particles[b].record() < particles[a].record() {
+        true => b,
+        false => a,
+    }
+}
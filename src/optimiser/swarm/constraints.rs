@@ -0,0 +1,179 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::{PsoError, Record};
+
+// the adaptive penalty coefficient stops growing at this multiple of its initial value
+const MAX_PENALTY_GROWTH: f64 = 1e12;
+
+type ConstraintFn = Arc<dyn Fn(&[f64]) -> f64 + Send + Sync>;
+
+// How a swarm compares points that break the job's constraints
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ConstraintHandling {
+    // cost + coefficient * total violation
+    StaticPenalty { coefficient: f64 },
+    // as the static penalty, but the coefficient is multiplied by growth every itteration a swarm's best is
+    // infeasible, up to 1e12 times the initial coefficient, and every stored record is penalised agian with it
+    AdaptivePenalty { initial: f64, growth: f64 },
+    // Deb's rules: feasible points beat infeasible ones, feasible points are compared by cost
+    // and infeasible points by total violation
+    #[default]
+    FeasibilityRules,
+}
+
+impl ConstraintHandling {
+    pub fn verify(&self) -> Result<(), PsoError> {
+        match *self {
+            Self::StaticPenalty { coefficient } | Self::AdaptivePenalty { initial: coefficient, .. }
+                if !(coefficient > 0.0 && coefficient.is_finite()) =>
+            {
+                Err(PsoError::InvalidPenalty(coefficient))
+            }
+            Self::AdaptivePenalty { growth, .. } if !(growth > 1.0 && growth.is_finite()) => {
+                Err(PsoError::InvalidPenaltyGrowth(growth))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for ConstraintHandling {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::StaticPenalty { coefficient } => write!(f, "static penalty ({})", coefficient),
+            Self::AdaptivePenalty { initial, growth } => {
+                write!(f, "adaptive penalty (from {}, growing by {})", initial, growth)
+            }
+            Self::FeasibilityRules => write!(f, "feasibility rules"),
+        }
+    }
+}
+
+// Inequality constraints g(x) <= 0 of a job
+#[derive(Clone, Default)]
+pub struct Constraints {
+    functions: Vec<ConstraintFn>,
+}
+
+impl Constraints {
+    pub fn push<G>(&mut self, constraint: G)
+    where
+        G: Fn(&[f64]) -> f64 + Send + Sync + 'static,
+    {
+        self.functions.push(Arc::new(constraint));
+    }
+
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    // sum of the amounts by which each constraint is broken at position; a constraint that can't be evaluated
+    // there (NaN or infinite) is broken infinitely, so the point never counts as feasible
+    pub fn violation(&self, position: &[f64]) -> f64 {
+        self.functions
+            .iter()
+            .map(|g| match g(position) {
+                value if value.is_finite() => value.max(0.0),
+                _ => f64::INFINITY,
+            })
+            .sum()
+    }
+}
+
+impl fmt::Debug for Constraints {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Constraints({})", self.functions.len())
+    }
+}
+
+// Constraint state of a job, cloned into each of its swarms: turns costs into records, and holds the adaptive
+// penalty coefficient, which is shared by every swarm so that their records stay comparable
+#[derive(Clone)]
+pub struct ConstraintHandler {
+    constraints: Constraints,
+    handling: ConstraintHandling,
+    job_coefficient: Arc<AtomicU64>,
+    // the coefficient this swarm's records are currently penalised with
+    coefficient: f64,
+}
+
+impl ConstraintHandler {
+    pub fn new(constraints: &Constraints, handling: ConstraintHandling) -> Self {
+        let coefficient = match handling {
+            ConstraintHandling::StaticPenalty { coefficient } => coefficient,
+            ConstraintHandling::AdaptivePenalty { initial, .. } => initial,
+            ConstraintHandling::FeasibilityRules => 0.0,
+        };
+
+        ConstraintHandler {
+            constraints: constraints.clone(),
+            handling,
+            job_coefficient: Arc::new(AtomicU64::new(coefficient.to_bits())),
+            coefficient,
+        }
+    }
+
+    // the record of a point, left without its location so that every evaluation doesn't allocate one:
+    // Particle::tell_record copies the location in only when the record becomes a personal best
+    pub fn score(&self, cost: f64, position: &[f64]) -> Record {
+        if self.constraints.is_empty() {
+            return Record::new(cost, &[]);
+        }
+
+        let violation = self.constraints.violation(position);
+        let mut record = Record::constrained(cost, violation, None, &[]);
+        self.penalise(&mut record);
+        record
+    }
+
+    // re-scores a record with this swarm's coefficient, unless the job compares records by Deb's rules
+    pub fn penalise(&self, record: &mut Record) {
+        if self.handling != ConstraintHandling::FeasibilityRules && !self.constraints.is_empty() {
+            record.penalise(self.coefficient);
+        }
+    }
+
+    // a copy of the record scored with the job's latest coefficient, for comparing the results of several swarms
+    pub fn rescored(&self, record: &Record) -> Record {
+        let mut record = record.clone();
+        if self.handling != ConstraintHandling::FeasibilityRules && !self.constraints.is_empty() {
+            record.penalise(f64::from_bits(self.job_coefficient.load(Ordering::SeqCst)));
+        }
+        record
+    }
+
+    // called once an itteration with the swarm's best record: grows the job's coefficient while that record is
+    // infeasible (at most once for all the swarms that saw the same coefficient), and returns the job's coefficient
+    // if it differs from the one this swarm's records are penalised with
+    pub fn adapt(&mut self, best: &Record) -> Option<f64> {
+        let (initial, growth) = match self.handling {
+            ConstraintHandling::AdaptivePenalty { initial, growth } => (initial, growth),
+            _ => return None,
+        };
+
+        if !best.is_blank() && !best.is_feasible() {
+            let grown = (self.coefficient * growth).min(initial * MAX_PENALTY_GROWTH);
+            let _ = self.job_coefficient.compare_exchange(
+                self.coefficient.to_bits(),
+                grown.to_bits(),
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            );
+        }
+
+        let job_coefficient = f64::from_bits(self.job_coefficient.load(Ordering::SeqCst));
+        match job_coefficient != self.coefficient {
+            true => {
+                self.coefficient = job_coefficient;
+                Some(job_coefficient)
+            }
+            false => None,
+        }
+    }
+}
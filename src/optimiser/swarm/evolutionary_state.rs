@@ -0,0 +1,213 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+
+use super::particle::{standard_normal, Particle};
+use super::Record;
+
+const COEFF_LIMITS: [f64; 2] = [1.5, 2.5];
+const MAX_COEFF_SUM: f64 = 4.0;
+const ELITIST_SIGMA: [f64; 2] = [1.0, 0.1];
+
+// Evolutionary states of an adaptive (APSO) swarm, estimated every itteration from how spread out the particles
+// are around the swarm's best particle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EvolutionaryState {
+    Exploration,
+    Exploitation,
+    Convergence,
+    JumpingOut,
+}
+
+impl EvolutionaryState {
+    // states are expected to follow each other in this order
+    fn next(self) -> Self {
+        match self {
+            Self::Exploration => Self::Exploitation,
+            Self::Exploitation => Self::Convergence,
+            Self::Convergence => Self::JumpingOut,
+            Self::JumpingOut => Self::Exploration,
+        }
+    }
+
+    // fuzzy membership of the evolutionary factor f in the state
+    fn membership(self, f: f64) -> f64 {
+        match self {
+            Self::Exploration => match f {
+                f if f <= 0.4 => 0.0,
+                f if f <= 0.6 => 5.0 * f - 2.0,
+                f if f <= 0.7 => 1.0,
+                f if f <= 0.8 => -10.0 * f + 8.0,
+                _ => 0.0,
+            },
+            Self::Exploitation => match f {
+                f if f <= 0.2 => 0.0,
+                f if f <= 0.3 => 10.0 * f - 2.0,
+                f if f <= 0.4 => 1.0,
+                f if f <= 0.6 => -5.0 * f + 3.0,
+                _ => 0.0,
+            },
+            Self::Convergence => match f {
+                f if f <= 0.1 => 1.0,
+                f if f <= 0.3 => -5.0 * f + 1.5,
+                _ => 0.0,
+            },
+            Self::JumpingOut => match f {
+                f if f <= 0.7 => 0.0,
+                f if f <= 0.9 => 5.0 * f - 3.5,
+                _ => 1.0,
+            },
+        }
+    }
+
+    // change of the local and tribal coefficients, in units of the acceleration rate
+    fn coeff_steps(self) -> [f64; 2] {
+        match self {
+            Self::Exploration => [1.0, -1.0],
+            Self::Exploitation => [0.5, -0.5],
+            Self::Convergence => [0.5, 0.5],
+            Self::JumpingOut => [-1.0, 1.0],
+        }
+    }
+}
+
+impl fmt::Display for EvolutionaryState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Exploration => write!(f, "exploration"),
+            Self::Exploitation => write!(f, "exploitation"),
+            Self::Convergence => write!(f, "convergence"),
+            Self::JumpingOut => write!(f, "jumping-out"),
+        }
+    }
+}
+
+// Per-swarm APSO state: the last estimated evolutionary state
+pub struct StateEstimator {
+    state: EvolutionaryState,
+}
+
+impl StateEstimator {
+    pub fn new() -> Self {
+        StateEstimator { state: EvolutionaryState::Exploration }
+    }
+
+    pub fn state(&self) -> EvolutionaryState {
+        self.state
+    }
+
+    // classifies the swarm, then sets the inertia from the evolutionary factor and steps the local and
+    // tribal coefficients towards the state's strategy
+    pub fn adapt(&mut self, particles: &[Particle], motion_coeffs: &mut [f64; 4], rng: &mut StdRng) {
+        let f = evolutionary_factor(particles);
+        self.state = self.classify(f);
+
+        let rate = rng.gen_range(0.05, 0.1);
+        let steps = self.state.coeff_steps();
+        for (coeff, step) in motion_coeffs[0..2].iter_mut().zip(steps.iter()) {
+            *coeff = (*coeff + step * rate).clamp(COEFF_LIMITS[0], COEFF_LIMITS[1]);
+        }
+
+        let coeff_sum = motion_coeffs[0] + motion_coeffs[1];
+        if coeff_sum > MAX_COEFF_SUM {
+            motion_coeffs[0] *= MAX_COEFF_SUM / coeff_sum;
+            motion_coeffs[1] *= MAX_COEFF_SUM / coeff_sum;
+        }
+
+        motion_coeffs[3] = 1.0 / (1.0 + 1.5 * (-2.6 * f).exp());
+    }
+
+    // where f falls between two states, the previous state is kept if possible, then the one following it
+    fn classify(&self, f: f64) -> EvolutionaryState {
+        let candidates: Vec<EvolutionaryState> = [
+            EvolutionaryState::Exploration,
+            EvolutionaryState::Exploitation,
+            EvolutionaryState::Convergence,
+            EvolutionaryState::JumpingOut,
+        ]
+        .iter()
+        .copied()
+        .filter(|state| state.membership(f) > 0.0)
+        .collect();
+
+        if candidates.contains(&self.state) {
+            self.state
+        } else if candidates.contains(&self.state.next()) {
+            self.state.next()
+        } else {
+            candidates
+                .into_iter()
+                .fold(None, |best: Option<EvolutionaryState>, state| match best {
+                    Some(best) if best.membership(f) >= state.membership(f) => Some(best),
+                    _ => Some(state),
+                })
+                .unwrap_or(self.state)
+        }
+    }
+}
+
+impl Default for StateEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// (d_best - d_min) / (d_max - d_min), where d is a particle's mean distance to the others and d_best that of
+// the particle holding the swarm's best personal record
+fn evolutionary_factor(particles: &[Particle]) -> f64 {
+    if particles.len() < 2 {
+        return 0.0;
+    }
+
+    let mean_distances: Vec<f64> = particles
+        .iter()
+        .map(|p| {
+            particles
+                .iter()
+                .map(|other| distance(p.position(), other.position()))
+                .sum::<f64>()
+                / (particles.len() - 1) as f64
+        })
+        .collect();
+
+    let best_index = (0..particles.len())
+        .fold(0, |best, i| match particles[i].record() < particles[best].record() {
+            true => i,
+            false => best,
+        });
+
+    let d_min = mean_distances.iter().cloned().fold(f64::INFINITY, f64::min);
+    let d_max = mean_distances.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+    match d_max > d_min {
+        true => (mean_distances[best_index] - d_min) / (d_max - d_min),
+        false => 0.0,
+    }
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>().sqrt()
+}
+
+// elitist learning: one random dimension of the tribal best is pushed by a gaussian step scaled to the search
+// range, with a spread falling from 1.0 to 0.1 over max_itterations, and kept within the bounds
+pub fn elitist_perturbation(
+    tribal_record: &Record,
+    pos_ranges: &[[f64; 2]],
+    itteration: usize,
+    max_itterations: usize,
+    rng: &mut StdRng,
+) -> Vec<f64> {
+    let progress = match max_itterations {
+        0 => 1.0,
+        _ => (itteration as f64 / max_itterations as f64).min(1.0),
+    };
+    let sigma = ELITIST_SIGMA[0] - (ELITIST_SIGMA[0] - ELITIST_SIGMA[1]) * progress;
+
+    let mut position = tribal_record.get_location();
+    let d = rng.gen_range(0, position.len());
+    let [lower, upper] = pos_ranges[d];
+
+    position[d] = (position[d] + (upper - lower) * sigma * standard_normal(rng)).max(lower).min(upper);
+    position
+}
@@ -0,0 +1,113 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+
+// the adaptive inertia can move at most this many growth-factor steps away from its configured value
+const MAX_INERTIA_STEPS: i32 = 4;
+
+// How a swarm's inertia weight evolves over a job
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum InertiaSchedule {
+    // grown by the inertial growth factor while stagnating, shrunk while finding new records
+    #[default]
+    Adaptive,
+    // the configured inertia throughout
+    Constant,
+    // falls linearly from w_max to w_min over max_itterations
+    Linear { w_max: f64, w_min: f64 },
+    // decays from w_max towards w_min as exp(-decay * progress)
+    Exponential { w_max: f64, w_min: f64, decay: f64 },
+    // linearly decreasing envelope modulated by a logistic map
+    Chaotic { w_max: f64, w_min: f64 },
+    // drawn uniformly from [w_min, w_max) every itteration
+    Random { w_min: f64, w_max: f64 },
+}
+
+impl fmt::Display for InertiaSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Adaptive => write!(f, "adaptive"),
+            Self::Constant => write!(f, "constant"),
+            Self::Linear { w_max, w_min } => write!(f, "linear {} -> {}", w_max, w_min),
+            Self::Exponential { w_max, w_min, decay } => write!(f, "exponential {} -> {} (decay {})", w_max, w_min, decay),
+            Self::Chaotic { w_max, w_min } => write!(f, "chaotic {} -> {}", w_max, w_min),
+            Self::Random { w_min, w_max } => write!(f, "random in [{}, {})", w_min, w_max),
+        }
+    }
+}
+
+// Per-swarm inertia state: the schedule plus whatever it needs to remember between itterations
+pub struct InertiaWeight {
+    schedule: InertiaSchedule,
+    igf: f64,
+    bounds: [f64; 2],
+    chaos: f64,
+}
+
+impl InertiaWeight {
+    pub fn new(schedule: InertiaSchedule, inertia: f64, inertial_growth_factor: f64, rng: &mut StdRng) -> Self {
+        let igf = growth_factor(inertial_growth_factor);
+        let span = igf.powi(MAX_INERTIA_STEPS);
+
+        InertiaWeight {
+            schedule,
+            igf,
+            bounds: [inertia / span, inertia * span],
+            chaos: rng.gen_range(0.0, 1.0),
+        }
+    }
+
+    pub fn initial(&mut self, inertia: f64, max_itterations: usize, rng: &mut StdRng) -> f64 {
+        match self.schedule {
+            InertiaSchedule::Adaptive | InertiaSchedule::Constant => inertia,
+            _ => self.next(inertia, 0, max_itterations, 0, rng),
+        }
+    }
+
+    // inertia to use for the given itteration, starting from the inertia used for the last one
+    pub fn next(
+        &mut self,
+        inertia: f64,
+        itteration: usize,
+        max_itterations: usize,
+        no_new_record_count: i128,
+        rng: &mut StdRng,
+    ) -> f64 {
+        let progress = match max_itterations {
+            0 => 1.0,
+            _ => (itteration as f64 / max_itterations as f64).min(1.0),
+        };
+
+        match self.schedule {
+            InertiaSchedule::Adaptive => {
+                let inertia = match no_new_record_count {
+                    count if count < 2 => inertia / self.igf,
+                    count if count > 7 => inertia * self.igf,
+                    _ => inertia,
+                };
+                inertia.max(self.bounds[0]).min(self.bounds[1])
+            }
+            InertiaSchedule::Constant => inertia,
+            InertiaSchedule::Linear { w_max, w_min } => w_max - (w_max - w_min) * progress,
+            InertiaSchedule::Exponential { w_max, w_min, decay } => {
+                w_min + (w_max - w_min) * (-decay * progress).exp()
+            }
+            InertiaSchedule::Chaotic { w_max, w_min } => {
+                self.chaos = 4.0 * self.chaos * (1.0 - self.chaos);
+                (w_max - w_min) * (1.0 - progress) + w_min * self.chaos
+            }
+            InertiaSchedule::Random { w_min, w_max } => match w_min < w_max {
+                true => rng.gen_range(w_min, w_max),
+                false => w_min,
+            },
+        }
+    }
+}
+
+// growth factors below one are read as their reciprocal, so stagnation always grows the inertia
+fn growth_factor(igf: f64) -> f64 {
+    match igf > 0.0 {
+        true => igf.max(1.0 / igf),
+        false => 1.0,
+    }
+}
@@ -0,0 +1,235 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+
+// How a multi-objective swarm picks leaders from its archive, and which members it drops once the archive is full
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LeaderSelection {
+    // binary tournament favouring the least crowded member; the most crowded member is dropped
+    #[default]
+    CrowdingDistance,
+    // roulette wheel over the occupied hypercubes of an adaptive grid with the given divisions per objective,
+    // favouring sparsely populated cubes; a member of the most populated cube is dropped
+    Grid { divisions: usize },
+}
+
+impl fmt::Display for LeaderSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CrowdingDistance => write!(f, "crowding distance"),
+            Self::Grid { divisions } => write!(f, "grid ({} divisions)", divisions),
+        }
+    }
+}
+
+// A point of a multi-objective search with one cost per objective
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParetoRecord {
+    costs: Vec<f64>,
+    location: Vec<f64>,
+}
+
+impl ParetoRecord {
+    pub fn new(costs: Vec<f64>, location: &[f64]) -> Self {
+        ParetoRecord { costs, location: location.to_vec() }
+    }
+
+    pub fn costs(&self) -> &[f64] {
+        &self.costs
+    }
+
+    pub fn location(&self) -> &[f64] {
+        &self.location
+    }
+
+    // no worse in every objective and better in at least one
+    pub fn dominates(&self, other: &Self) -> bool {
+        let mut is_better = false;
+        for (cost, other_cost) in self.costs.iter().zip(other.costs.iter()) {
+            if cost > other_cost {
+                return false;
+            }
+            if cost < other_cost {
+                is_better = true;
+            }
+        }
+        is_better
+    }
+}
+
+impl fmt::Display for ParetoRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Costs of: {:?} @ \t {:?}", self.costs, self.location)
+    }
+}
+
+// External archive of the non-dominated points found so far, bounded to capacity members
+#[derive(Clone, Debug)]
+pub struct ParetoArchive {
+    capacity: usize,
+    leader_selection: LeaderSelection,
+    members: Vec<ParetoRecord>,
+}
+
+impl ParetoArchive {
+    pub fn new(capacity: usize, leader_selection: LeaderSelection) -> Self {
+        ParetoArchive {
+            capacity,
+            leader_selection,
+            members: Vec::new(),
+        }
+    }
+
+    pub fn members(&self) -> &[ParetoRecord] {
+        &self.members
+    }
+
+    // false if the candidate is dominated by, or equal to, a member
+    pub fn insert(&mut self, candidate: ParetoRecord, rng: &mut StdRng) -> bool {
+        if self
+            .members
+            .iter()
+            .any(|member| member.dominates(&candidate) || member.costs == candidate.costs)
+        {
+            return false;
+        }
+
+        self.members.retain(|member| !candidate.dominates(member));
+        self.members.push(candidate);
+
+        if self.members.len() > self.capacity {
+            self.drop_crowded(rng);
+        }
+        true
+    }
+
+    // true if any member of the other archive was accepted
+    pub fn merge(&mut self, other: &ParetoArchive, rng: &mut StdRng) -> bool {
+        let mut is_changed = false;
+        for member in other.members.iter() {
+            is_changed |= self.insert(member.clone(), rng);
+        }
+        is_changed
+    }
+
+    pub fn select_leader(&self, rng: &mut StdRng) -> Option<&ParetoRecord> {
+        if self.members.is_empty() {
+            return None;
+        }
+
+        match self.leader_selection {
+            LeaderSelection::CrowdingDistance => {
+                let distances = crowding_distances(&self.members);
+                let a = rng.gen_range(0, self.members.len());
+                let b = rng.gen_range(0, self.members.len());
+                match distances[b] > distances[a] {
+                    true => Some(&self.members[b]),
+                    false => Some(&self.members[a]),
+                }
+            }
+            LeaderSelection::Grid { divisions } => {
+                let cubes = hypercubes(&self.members, divisions);
+
+                // each occupied cube is weighted 10 / its population
+                let total: f64 = cubes.iter().map(|cube| 10.0 / cube.len() as f64).sum();
+                let mut spin = rng.gen_range(0.0, total);
+                for cube in cubes.iter() {
+                    spin -= 10.0 / cube.len() as f64;
+                    if spin < 0.0 {
+                        return Some(&self.members[cube[rng.gen_range(0, cube.len())]]);
+                    }
+                }
+
+                let cube = &cubes[cubes.len() - 1];
+                Some(&self.members[cube[rng.gen_range(0, cube.len())]])
+            }
+        }
+    }
+
+    fn drop_crowded(&mut self, rng: &mut StdRng) {
+        let index = match self.leader_selection {
+            LeaderSelection::CrowdingDistance => {
+                let distances = crowding_distances(&self.members);
+                (0..self.members.len()).fold(0, |most_crowded, i| match distances[i] < distances[most_crowded] {
+                    true => i,
+                    false => most_crowded,
+                })
+            }
+            LeaderSelection::Grid { divisions } => {
+                let cubes = hypercubes(&self.members, divisions);
+                let fullest = cubes
+                    .iter()
+                    .fold(&cubes[0], |fullest, cube| match cube.len() > fullest.len() {
+                        true => cube,
+                        false => fullest,
+                    });
+                fullest[rng.gen_range(0, fullest.len())]
+            }
+        };
+
+        self.members.remove(index);
+    }
+}
+
+// sum over the objectives of the normalised gap between each member's neighbours, infinite for the extremes
+fn crowding_distances(members: &[ParetoRecord]) -> Vec<f64> {
+    if members.len() < 3 {
+        return vec![f64::INFINITY; members.len()];
+    }
+
+    let mut distances = vec![0.0; members.len()];
+    let num_objectives = members[0].costs.len();
+    for m in 0..num_objectives {
+        let mut order: Vec<usize> = (0..members.len()).collect();
+        order.sort_by(|&a, &b| {
+            members[a].costs[m]
+                .partial_cmp(&members[b].costs[m])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let first = order[0];
+        let last = order[order.len() - 1];
+        let range = members[last].costs[m] - members[first].costs[m];
+        distances[first] = f64::INFINITY;
+        distances[last] = f64::INFINITY;
+
+        if range > 0.0 {
+            for k in 1..order.len() - 1 {
+                distances[order[k]] += (members[order[k + 1]].costs[m] - members[order[k - 1]].costs[m]) / range;
+            }
+        }
+    }
+    distances
+}
+
+// indices of the members in each occupied cube of a grid spanning the members' costs
+fn hypercubes(members: &[ParetoRecord], divisions: usize) -> Vec<Vec<usize>> {
+    let num_objectives = members[0].costs.len();
+    let limits: Vec<[f64; 2]> = (0..num_objectives)
+        .map(|m| {
+            members.iter().fold([f64::INFINITY, f64::NEG_INFINITY], |[lower, upper], member| {
+                [lower.min(member.costs[m]), upper.max(member.costs[m])]
+            })
+        })
+        .collect();
+
+    let mut cubes: HashMap<Vec<usize>, Vec<usize>> = HashMap::new();
+    for (i, member) in members.iter().enumerate() {
+        let cube: Vec<usize> = member
+            .costs
+            .iter()
+            .zip(limits.iter())
+            .map(|(cost, [lower, upper])| match upper > lower {
+                true => (((cost - lower) / (upper - lower) * divisions as f64) as usize).min(divisions - 1),
+                false => 0,
+            })
+            .collect();
+        cubes.entry(cube).or_default().push(i);
+    }
+
+    // sorted so that seeded runs repeat regardless of the map's iteration order
+    let mut cubes: Vec<Vec<usize>> = cubes.into_values().collect();
+    cubes.sort_unstable();
+    cubes
+}
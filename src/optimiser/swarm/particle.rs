@@ -1,11 +1,12 @@
-use super::Record;
-use rand::prelude::ThreadRng;
+use super::{EvaluationBudget, FipsWeighting, Record};
+use rand::rngs::StdRng;
 use rand::Rng;
 use std::sync::Arc;
 
 pub struct Particle {
     pos_vel: Vec<f64>,
     rec: Record,
+    neighbourhood_best: Record,
     num_vars: usize,
 }
 
@@ -15,6 +16,7 @@ impl Particle {
         Particle {
             pos_vel: pos.clone(),
             rec: Record::blank(num_vars),
+            neighbourhood_best: Record::blank(num_vars),
             num_vars: num_vars,
         }
     }
@@ -22,15 +24,15 @@ impl Particle {
     pub fn update_colab(
         &mut self,
         motion_coeffs: &[f64; 4],
-        rng: &mut ThreadRng,
-        stoch: f64,
+        rng: &mut StdRng,
+        stoch: [f64; 2],
         tribal_best: &Vec<f64>,
         global_best: &Vec<f64>,
     ) {
         let rands: Vec<Vec<f64>> = (0..3)
             .map(|_| {
                 (0..self.num_vars)
-                    .map(|_| rng.gen_range(0.2, stoch))
+                    .map(|_| stochastic_factor(rng, stoch))
                     .collect()
             })
             .collect();
@@ -49,14 +51,14 @@ impl Particle {
     pub fn update_indep(
         &mut self,
         motion_coeffs: &[f64; 4],
-        rng: &mut ThreadRng,
-        stoch: f64,
+        rng: &mut StdRng,
+        stoch: [f64; 2],
         best: &Vec<f64>,
     ) {
         let rands: Vec<Vec<f64>> = (0..2)
             .map(|_| {
                 (0..self.num_vars)
-                    .map(|_| rng.gen_range(0.2, stoch))
+                    .map(|_| stochastic_factor(rng, stoch))
                     .collect()
             })
             .collect();
@@ -71,30 +73,268 @@ impl Particle {
         }
     }
 
+    // multi-objective swarms keep personal bests outside the particle, and draw the tribal and global bests
+    // as leaders from their Pareto archives
+    pub fn update_pareto(
+        &mut self,
+        motion_coeffs: &[f64; 4],
+        rng: &mut StdRng,
+        stoch: [f64; 2],
+        personal_best: &[f64],
+        tribal_leader: &[f64],
+        global_leader: Option<&[f64]>,
+    ) {
+        for pi in 0..self.num_vars {
+            let vi = pi + self.num_vars;
+            self.pos_vel[pi] += self.pos_vel[vi];
+
+            self.pos_vel[vi] = motion_coeffs[3] * self.pos_vel[vi]
+                + motion_coeffs[0] * stochastic_factor(rng, stoch) * (personal_best[pi] - self.pos_vel[pi])
+                + motion_coeffs[1] * stochastic_factor(rng, stoch) * (tribal_leader[pi] - self.pos_vel[pi]);
+
+            if let Some(global_leader) = global_leader {
+                self.pos_vel[vi] +=
+                    motion_coeffs[2] * stochastic_factor(rng, stoch) * (global_leader[pi] - self.pos_vel[pi]);
+            }
+        }
+    }
+
+    // Standard PSO 2011: the new point is drawn uniformly from the hypersphere centred on the centre of gravity
+    // of the particle and its attractors, with the particle on its surface, so no coordinate axis is favoured
+    pub fn update_spso(&mut self, motion_coeffs: &[f64; 4], rng: &mut StdRng, attractors: &[&Vec<f64>]) {
+        for pi in 0..self.num_vars {
+            let vi = pi + self.num_vars;
+            self.pos_vel[pi] += self.pos_vel[vi];
+        }
+
+        let personal_best = self.rec.get_location();
+
+        // an attractor equal to one already counted (e.g. the personal best of the swarm's best particle) counts once
+        let mut distinct: Vec<(f64, &Vec<f64>)> = vec![(motion_coeffs[0], &personal_best)];
+        for (coeff, attractor) in motion_coeffs[1..3].iter().zip(attractors.iter()) {
+            if !distinct.iter().any(|(_, counted)| counted == attractor) {
+                distinct.push((*coeff, attractor));
+            }
+        }
+
+        let weight = 1.0 / (distinct.len() as f64 + 1.0);
+        let mut centre = self.position().to_vec();
+        for (coeff, attractor) in distinct.iter() {
+            for pi in 0..self.num_vars {
+                centre[pi] += weight * coeff * (attractor[pi] - self.pos_vel[pi]);
+            }
+        }
+
+        let radius = centre
+            .iter()
+            .zip(self.position())
+            .map(|(c, x)| (c - x).powi(2))
+            .sum::<f64>()
+            .sqrt();
+        let sample = sample_hypersphere(rng, &centre, radius);
+
+        for (pi, x) in sample.iter().enumerate() {
+            let vi = pi + self.num_vars;
+            self.pos_vel[vi] = motion_coeffs[3] * self.pos_vel[vi] + x - self.pos_vel[pi];
+        }
+    }
+
+    // the attraction coefficients are summed into phi, which is split between the informants' personal bests
+    pub fn update_fips(
+        &mut self,
+        motion_coeffs: &[f64; 4],
+        rng: &mut StdRng,
+        stoch: [f64; 2],
+        informants: &[&Record],
+        weighting: FipsWeighting,
+    ) {
+        let phi: f64 = motion_coeffs[0..3].iter().sum();
+
+        let weights: Vec<f64> = match weighting {
+            FipsWeighting::Equal => vec![1.0; informants.len()],
+            // ranked rather than scaled by cost, so the pull does not depend on the scale of the cost function
+            FipsWeighting::Fitness => informants
+                .iter()
+                .map(|record| {
+                    let num_worse = informants.iter().filter(|other| *record < **other).count();
+                    1.0 + num_worse as f64
+                })
+                .collect(),
+        };
+        let total_weight: f64 = weights.iter().sum();
+        let shares: Vec<f64> = weights.iter().map(|weight| phi * weight / total_weight).collect();
+
+        for pi in 0..self.num_vars {
+            let vi = pi + self.num_vars;
+            self.pos_vel[pi] += self.pos_vel[vi];
+
+            let mut pull = 0.0;
+            for (record, share) in informants.iter().zip(shares.iter()) {
+                pull += share * stochastic_factor(rng, stoch) * (record.pos(pi) - self.pos_vel[pi]);
+            }
+
+            self.pos_vel[vi] = motion_coeffs[3] * self.pos_vel[vi] + pull;
+        }
+    }
+
+    // Comprehensive Learning PSO: the local coefficient pulls each dimension towards its exemplar,
+    // and the global coefficient towards the global record of a collaborative swarm
+    pub fn update_clpso(
+        &mut self,
+        motion_coeffs: &[f64; 4],
+        rng: &mut StdRng,
+        stoch: [f64; 2],
+        exemplar: &[f64],
+        global_best: Option<&[f64]>,
+    ) {
+        for pi in 0..self.num_vars {
+            let vi = pi + self.num_vars;
+            self.pos_vel[pi] += self.pos_vel[vi];
+
+            self.pos_vel[vi] = motion_coeffs[3] * self.pos_vel[vi]
+                + motion_coeffs[0] * stochastic_factor(rng, stoch) * (exemplar[pi] - self.pos_vel[pi]);
+
+            if let Some(global_best) = global_best {
+                self.pos_vel[vi] +=
+                    motion_coeffs[2] * stochastic_factor(rng, stoch) * (global_best[pi] - self.pos_vel[pi]);
+            }
+        }
+    }
+
+    // Kennedy's bare-bones PSO: velocity free, each coordinate is drawn from a normal distribution centred between
+    // the personal and social bests, with their distance as its standard deviation
+    pub fn update_bare_bones(&mut self, rng: &mut StdRng, social_best: &[f64]) {
+        // the initial positions are evaluated before any sampling
+        if self.rec.is_blank() {
+            for vi in self.num_vars..2 * self.num_vars {
+                self.pos_vel[vi] = 0.0;
+            }
+            return;
+        }
+
+        for (pi, best) in social_best.iter().enumerate() {
+            let vi = pi + self.num_vars;
+            let mean = 0.5 * (self.rec.pos(pi) + best);
+            let std_dev = (self.rec.pos(pi) - best).abs();
+
+            self.pos_vel[pi] = mean + std_dev * standard_normal(rng);
+            self.pos_vel[vi] = 0.0;
+        }
+    }
+
+    // Quantum-behaved PSO: velocity free, each coordinate is drawn around a random point between the personal and
+    // social bests, spread by the contraction-expansion coefficient times its distance from the mean personal best
+    pub fn update_qpso(&mut self, rng: &mut StdRng, beta: f64, mean_best: &[f64], social_best: &[f64]) {
+        // the initial positions are evaluated before any sampling
+        if self.rec.is_blank() {
+            for vi in self.num_vars..2 * self.num_vars {
+                self.pos_vel[vi] = 0.0;
+            }
+            return;
+        }
+
+        for pi in 0..self.num_vars {
+            let vi = pi + self.num_vars;
+            let phi: f64 = rng.gen();
+            let attractor = phi * self.rec.pos(pi) + (1.0 - phi) * social_best[pi];
+
+            let u: f64 = rng.gen_range(f64::EPSILON, 1.0);
+            let spread = beta * (mean_best[pi] - self.pos_vel[pi]).abs() * (1.0 / u).ln();
+
+            self.pos_vel[pi] = match rng.gen::<bool>() {
+                true => attractor + spread,
+                false => attractor - spread,
+            };
+            self.pos_vel[vi] = 0.0;
+        }
+    }
+
+    // moves the particle without changing its velocity
+    pub fn relocate(&mut self, position: &[f64]) {
+        self.pos_vel[0..self.num_vars].copy_from_slice(position);
+    }
+
     pub fn pos_vel(&mut self) -> &mut [f64] {
         &mut self.pos_vel[..]
     }
 
-    fn pos_slice(&self) -> &[f64] {
+    pub fn position(&self) -> &[f64] {
         &self.pos_vel[0..self.num_vars]
     }
 
-    pub fn test_cost<F>(&mut self, get_cost: &Arc<F>) -> Option<f64>
+    pub fn velocity(&self) -> &[f64] {
+        &self.pos_vel[self.num_vars..]
+    }
+
+    pub fn record(&self) -> &Record {
+        &self.rec
+    }
+
+    // None once the job's evaluation budget is spent
+    pub fn test_cost<F>(&self, get_cost: &Arc<F>, budget: &EvaluationBudget) -> Option<f64>
     where
         F: Fn(&[f64]) -> f64,
     {
-        let cost = get_cost(self.pos_slice());
+        match budget.try_take() {
+            true => Some(get_cost(self.position())),
+            false => None,
+        }
+    }
 
-        return match self.rec.loses_to(cost) {
+    // true if the record of the particle's current position becomes its personal best
+    pub fn tell_record(&mut self, record: Record) -> bool {
+        match self.rec.loses_to(&record) {
             true => {
-                self.rec = Record::new(cost, self.pos_slice());
-                Some(cost)
+                self.rec = record.with_location(self.position());
+                true
             }
-            false => None,
-        };
+            false => false,
+        }
+    }
+
+    // re-scores the personal and neighbourhood bests of a penalised job with a new penalty coefficient
+    pub fn penalise(&mut self, coefficient: f64) {
+        self.rec.penalise(coefficient);
+        self.neighbourhood_best.penalise(coefficient);
     }
 
     pub fn get_record(&self) -> Record {
         self.rec.clone()
     }
-}
\ No newline at end of file
+
+    // best personal record among the particle's informants, kept up to date by non-global topologies
+    pub fn neighbourhood_best(&self) -> &Record {
+        &self.neighbourhood_best
+    }
+
+    pub fn set_neighbourhood_best(&mut self, record: Record) {
+        self.neighbourhood_best = record;
+    }
+}
+
+fn stochastic_factor(rng: &mut StdRng, stoch: [f64; 2]) -> f64 {
+    match stoch[0] < stoch[1] {
+        true => rng.gen_range(stoch[0], stoch[1]),
+        false => stoch[0],
+    }
+}
+
+// uniform over the volume of the ball: a gaussian direction scaled by radius * U^(1/d)
+fn sample_hypersphere(rng: &mut StdRng, centre: &[f64], radius: f64) -> Vec<f64> {
+    let direction: Vec<f64> = centre.iter().map(|_| standard_normal(rng)).collect();
+    let norm = direction.iter().map(|d| d.powi(2)).sum::<f64>().sqrt();
+    let scale = match norm > 0.0 {
+        true => radius * rng.gen::<f64>().powf(1.0 / centre.len() as f64) / norm,
+        false => 0.0,
+    };
+
+    centre.iter().zip(direction.iter()).map(|(c, d)| c + scale * d).collect()
+}
+
+// Box-Muller transform
+pub fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1: f64 = rng.gen_range(f64::EPSILON, 1.0);
+    let u2: f64 = rng.gen();
+
+    (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+}
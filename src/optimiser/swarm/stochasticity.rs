@@ -0,0 +1,65 @@
+use std::fmt;
+use std::sync::Arc;
+
+// Range [low, high) from which the random factors of every particle update are drawn,
+// as a function of the job's progress (itteration / max_itterations, from 0 to 1)
+#[derive(Clone, Default)]
+pub enum StochasticitySchedule {
+    // [0.2, arctan + gaussian curve): wide early on, narrowing with a brief burst at ~70% progress
+    #[default]
+    ArctanGaussian,
+    // textbook PSO: [0, 1) throughout
+    Uniform,
+    Custom(Arc<dyn Fn(f64) -> [f64; 2] + Send + Sync>),
+}
+
+impl StochasticitySchedule {
+    pub fn custom<F>(schedule: F) -> Self
+    where
+        F: Fn(f64) -> [f64; 2] + Send + Sync + 'static,
+    {
+        StochasticitySchedule::Custom(Arc::new(schedule))
+    }
+
+    pub fn range(&self, itteration: usize, max_itterations: usize) -> [f64; 2] {
+        let progress = match max_itterations {
+            0 => 1.0,
+            _ => (itteration as f64 / max_itterations as f64).min(1.0),
+        };
+
+        match self {
+            Self::ArctanGaussian => [0.2, arctan_gaussian(progress)],
+            Self::Uniform => [0.0, 1.0],
+            Self::Custom(schedule) => schedule(progress),
+        }
+    }
+}
+
+impl fmt::Debug for StochasticitySchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ArctanGaussian => write!(f, "ArctanGaussian"),
+            Self::Uniform => write!(f, "Uniform"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl fmt::Display for StochasticitySchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ArctanGaussian => write!(f, "arctan-gaussian"),
+            Self::Uniform => write!(f, "uniform"),
+            Self::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
+
+fn arctan_gaussian(progress: f64) -> f64 {
+    assert!((0.0..=1.0).contains(&progress));
+
+    let arctan = 2.0 * (1.0 - (std::f64::consts::PI * (progress - 0.5)).atan()) + 0.25;
+    let guass = (-((progress - std::f64::consts::FRAC_1_SQRT_2) / 0.025).powi(2)).exp();
+
+    arctan + guass
+}
@@ -1,13 +1,20 @@
-use rand::prelude::ThreadRng;
+use rand::rngs::StdRng;
 use rand::Rng;
 use std::fmt;
 
+use super::{InertiaSchedule, PsoError, StochasticitySchedule, Topology};
+
 #[derive(Clone, Debug)]
 pub struct SwarmConfig{
     motion_coeffs: [f64; 4],
     igf: f64,
     wbf: f64,
     tgse: Option<usize>,
+    inertia_schedule: InertiaSchedule,
+    stochasticity_schedule: StochasticitySchedule,
+    velocity_update_rule: VelocityUpdateRule,
+    topology: Topology,
+    evolutionary_state_estimation: bool,
 }
 
 impl SwarmConfig {
@@ -31,6 +38,11 @@ impl SwarmConfig {
             igf: igf,
             wbf: wbf,
             tgse: Some(tribal_global_share_every),
+            inertia_schedule: InertiaSchedule::default(),
+            stochasticity_schedule: StochasticitySchedule::default(),
+            velocity_update_rule: VelocityUpdateRule::default(),
+            topology: Topology::default(),
+            evolutionary_state_estimation: false,
         }
     }
 
@@ -51,7 +63,12 @@ impl SwarmConfig {
             motion_coeffs: [l, t, 0.0, i],
             igf: igf,
             wbf: wbf,
-            tgse: None
+            tgse: None,
+            inertia_schedule: InertiaSchedule::default(),
+            stochasticity_schedule: StochasticitySchedule::default(),
+            velocity_update_rule: VelocityUpdateRule::default(),
+            topology: Topology::default(),
+            evolutionary_state_estimation: false,
         }
     }
 
@@ -61,6 +78,11 @@ impl SwarmConfig {
             igf: 1.125,
             wbf: 0.125,
             tgse: Some(8),
+            inertia_schedule: InertiaSchedule::default(),
+            stochasticity_schedule: StochasticitySchedule::default(),
+            velocity_update_rule: VelocityUpdateRule::default(),
+            topology: Topology::default(),
+            evolutionary_state_estimation: false,
         }
     }
 
@@ -70,7 +92,86 @@ impl SwarmConfig {
             igf: 1.125,
             wbf: 0.125,
             tgse: None,
+            inertia_schedule: InertiaSchedule::default(),
+            stochasticity_schedule: StochasticitySchedule::default(),
+            velocity_update_rule: VelocityUpdateRule::default(),
+            topology: Topology::default(),
+            evolutionary_state_estimation: false,
+        }
+    }
+
+    // the inertia adapts by the inertial growth factor by default; the other schedules ignore that factor
+    pub fn with_inertia_schedule(mut self, inertia_schedule: InertiaSchedule) -> Self {
+        self.inertia_schedule = inertia_schedule;
+        self
+    }
+
+    pub fn with_stochasticity_schedule(mut self, stochasticity_schedule: StochasticitySchedule) -> Self {
+        self.stochasticity_schedule = stochasticity_schedule;
+        self
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    // Adaptive PSO: the local and tribal coefficients and the inertia are re-tuned every itteration from the swarm's
+    // estimated evolutionary state, overriding the inertia schedule, and a converging swarm's tribal best is
+    // perturbed by elitist learning, at the cost of one extra evaluation per itteration
+    pub fn with_evolutionary_state_estimation(mut self) -> Self {
+        self.evolutionary_state_estimation = true;
+        self
+    }
+
+    pub fn with_velocity_update_rule(self, velocity_update_rule: VelocityUpdateRule) -> Self {
+        self.try_with_velocity_update_rule(velocity_update_rule)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    // rules derived for random factors drawn from [0, 1) switch the stochasticity schedule to Uniform
+    pub fn try_with_velocity_update_rule(mut self, velocity_update_rule: VelocityUpdateRule) -> Result<Self, PsoError> {
+        self.velocity_update_rule = velocity_update_rule;
+        if velocity_update_rule.requires_uniform_stochasticity() {
+            self.stochasticity_schedule = StochasticitySchedule::Uniform;
+        }
+        self.verify()?;
+        Ok(self)
+    }
+
+    pub fn verify(&self) -> Result<(), PsoError> {
+        if self.constriction_factor().is_some() && self.phi() <= 4.0 {
+            return Err(PsoError::InvalidConstrictionPhi(self.phi()));
+        }
+        if self.velocity_update_rule.requires_uniform_stochasticity()
+            && !matches!(self.stochasticity_schedule, StochasticitySchedule::Uniform)
+        {
+            return Err(PsoError::NonUniformStochasticity(self.velocity_update_rule));
+        }
+        if self.evolutionary_state_estimation && self.velocity_update_rule != VelocityUpdateRule::Inertia {
+            return Err(PsoError::EvolutionaryStateRequiresInertia(self.velocity_update_rule));
+        }
+        if let VelocityUpdateRule::Qpso { beta_max, beta_min } = self.velocity_update_rule {
+            if !(beta_min > 0.0 && beta_min <= beta_max) {
+                return Err(PsoError::InvalidContractionExpansion { max: beta_max, min: beta_min });
+            }
         }
+        Ok(())
+    }
+
+    // multi-objective swarms draw leaders from their archive with the inertia or constriction updates only
+    pub fn verify_multi_objective(&self) -> Result<(), PsoError> {
+        match self.velocity_update_rule {
+            VelocityUpdateRule::Inertia | VelocityUpdateRule::Constriction => (),
+            rule => return Err(PsoError::MultiObjectiveUnsupported(format!("the {} velocity update", rule))),
+        }
+        if self.topology != Topology::Global {
+            return Err(PsoError::MultiObjectiveUnsupported(format!("the {} topology", self.topology)));
+        }
+        if self.evolutionary_state_estimation {
+            return Err(PsoError::MultiObjectiveUnsupported("evolutionary state estimation".to_string()));
+        }
+        Ok(())
     }
 
     pub fn motion_coeffs(&self) -> [f64;4] {
@@ -81,6 +182,45 @@ impl SwarmConfig {
         self.igf
     }
 
+    pub fn inertia_schedule(&self) -> InertiaSchedule {
+        self.inertia_schedule
+    }
+
+    pub fn stochasticity_schedule(&self) -> StochasticitySchedule {
+        self.stochasticity_schedule.clone()
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn velocity_update_rule(&self) -> VelocityUpdateRule {
+        self.velocity_update_rule
+    }
+
+    pub fn evolutionary_state_estimation(&self) -> bool {
+        self.evolutionary_state_estimation
+    }
+
+    // Clerc-Kennedy constriction coefficient chi, for configurations using the constriction or FIPS rules
+    pub fn constriction_factor(&self) -> Option<f64> {
+        match self.velocity_update_rule {
+            VelocityUpdateRule::Inertia
+            | VelocityUpdateRule::Spso2011
+            | VelocityUpdateRule::Clpso { .. }
+            | VelocityUpdateRule::BareBones
+            | VelocityUpdateRule::Qpso { .. } => None,
+            VelocityUpdateRule::Constriction | VelocityUpdateRule::Fips(_) => {
+                let phi = self.phi();
+                Some(2.0 / (2.0 - phi - (phi * phi - 4.0 * phi).sqrt()).abs())
+            }
+        }
+    }
+
+    fn phi(&self) -> f64 {
+        self.motion_coeffs[0] + self.motion_coeffs[1] + self.motion_coeffs[2]
+    }
+
     pub fn wall_bounce_factor(&self) -> f64 {
         self.wbf * -1.0
     }
@@ -111,10 +251,80 @@ impl fmt::Display for SwarmConfig {
             }
         }
 
-        write!(f, "\t \t Motion coefficients: {:?}, inertial growth factor: {}, wall bounce factor: {}", 
-            self.motion_coeffs, self.igf, self.wbf,
-        )
+        write!(f, "\t \t Motion coefficients: {:?}, inertial growth factor: {}, wall bounce factor: {}, {} inertia, {} stochasticity, {} velocity update, {} topology", 
+            self.motion_coeffs, self.igf, self.wbf, self.inertia_schedule, self.stochasticity_schedule,
+            self.velocity_update_rule, self.topology,
+        )?;
+
+        if self.evolutionary_state_estimation {
+            write!(f, ", evolutionary state estimation")?;
+        }
+        Ok(())
+
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum VelocityUpdateRule {
+    // v = w * v + attraction terms
+    #[default]
+    Inertia,
+    // v = chi * (v + attraction terms), with chi derived from phi, the sum of the attraction coefficients;
+    // chi takes the place of the inertia, so the inertia coefficient and schedule go unused
+    Constriction,
+    // Standard PSO 2011: v = w * v + x' - x, with x' drawn from a hypersphere around the attractors' centre of gravity
+    Spso2011,
+    // Fully Informed Particle Swarm: constriction form, with phi shared among the personal bests of every
+    // informant given by the swarm's topology instead of a single best; as with constriction, chi replaces the inertia
+    Fips(FipsWeighting),
+    // Comprehensive Learning PSO: each dimension is pulled, with the local coefficient, towards the personal best
+    // of its own exemplar particle; exemplars are re-drawn once a particle's best has not improved for refresh_gap
+    // itterations
+    Clpso { refresh_gap: usize },
+    // Kennedy's bare-bones PSO: positions are sampled around the personal and tribal bests rather than moved by a
+    // velocity, so the motion coefficients, inertia and stochasticity go unused
+    BareBones,
+    // Quantum-behaved PSO: positions are drawn around a point between the personal and tribal bests, spread by the
+    // distance from the swarm's mean personal best; the contraction-expansion coefficient falls linearly from
+    // beta_max to beta_min over max_itterations, and the motion coefficients, inertia and stochasticity go unused
+    Qpso { beta_max: f64, beta_min: f64 },
+}
+
+impl VelocityUpdateRule {
+    // the canonical coefficients of these rules only hold for random factors drawn from [0, 1)
+    pub fn requires_uniform_stochasticity(&self) -> bool {
+        matches!(self, Self::Constriction | Self::Fips(_) | Self::Clpso { .. })
+    }
+}
+
+impl fmt::Display for VelocityUpdateRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Inertia => write!(f, "inertia"),
+            Self::Constriction => write!(f, "constriction"),
+            Self::Spso2011 => write!(f, "SPSO-2011"),
+            Self::Fips(weighting) => write!(f, "FIPS ({} weighting)", weighting),
+            Self::Clpso { refresh_gap } => write!(f, "CLPSO (refresh gap {})", refresh_gap),
+            Self::BareBones => write!(f, "bare-bones"),
+            Self::Qpso { beta_max, beta_min } => write!(f, "QPSO (beta {} -> {})", beta_max, beta_min),
+        }
+    }
+}
+
+// How FIPS divides phi among a particle's informants
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FipsWeighting {
+    Equal,
+    // weighted by rank, so better personal bests pull harder
+    Fitness,
+}
 
+impl fmt::Display for FipsWeighting {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Equal => write!(f, "equal"),
+            Self::Fitness => write!(f, "fitness"),
+        }
     }
 }
 
@@ -126,7 +336,12 @@ pub struct SwarmConfigDistribution {
     i: ParamDist,
     igf: ParamDist,
     wbf: ParamDist,
-    tgse: Option<usize>
+    tgse: Option<usize>,
+    inertia_schedule: InertiaSchedule,
+    stochasticity_schedule: StochasticitySchedule,
+    velocity_update_rule: VelocityUpdateRule,
+    topology: Topology,
+    evolutionary_state_estimation: bool,
 }
 
 impl SwarmConfigDistribution {
@@ -136,16 +351,30 @@ impl SwarmConfigDistribution {
         wall_bounce_factor: ParamDist,
         tribal_global_share_every: usize,
     ) -> Self {
+        Self::try_new_collaborative(
+            local, tribal, global,
+            inertial, inertial_growth_factor,
+            wall_bounce_factor,
+            tribal_global_share_every,
+        ).unwrap_or_else(|e| panic!("{}", e))
+    }
 
-        let l = correct_sine_dist(local);
-        let t = correct_sine_dist(tribal);
-        let g = correct_sine_dist(global);
-        let i = correct_sine_dist(inertial);
+    pub fn try_new_collaborative(
+        local: ParamDist, tribal: ParamDist, global: ParamDist,
+        inertial: ParamDist, inertial_growth_factor: ParamDist,
+        wall_bounce_factor: ParamDist,
+        tribal_global_share_every: usize,
+    ) -> Result<Self, PsoError> {
+
+        let l = correct_sine_dist(local)?;
+        let t = correct_sine_dist(tribal)?;
+        let g = correct_sine_dist(global)?;
+        let i = correct_sine_dist(inertial)?;
 
-        let ifg = correct_sine_dist(inertial_growth_factor);
-        let wbf = correct_sine_dist(wall_bounce_factor);
+        let ifg = correct_sine_dist(inertial_growth_factor)?;
+        let wbf = correct_sine_dist(wall_bounce_factor)?;
 
-        SwarmConfigDistribution{
+        Ok(SwarmConfigDistribution{
             l: l,
             t: t,
             g: g,
@@ -153,7 +382,12 @@ impl SwarmConfigDistribution {
             igf: ifg,
             wbf: wbf,
             tgse: Some(tribal_global_share_every),
-        }
+            inertia_schedule: InertiaSchedule::default(),
+            stochasticity_schedule: StochasticitySchedule::default(),
+            velocity_update_rule: VelocityUpdateRule::default(),
+            topology: Topology::default(),
+            evolutionary_state_estimation: false,
+        })
     }
 
     pub fn new_independant(
@@ -161,14 +395,26 @@ impl SwarmConfigDistribution {
         inertial: ParamDist, inertial_growth_factor: ParamDist,
         wall_bounce_factor: ParamDist,
     ) -> Self {
-        let l = correct_sine_dist(local);
-        let t = correct_sine_dist(tribal);
-        let i = correct_sine_dist(inertial);
+        Self::try_new_independant(
+            local, tribal,
+            inertial, inertial_growth_factor,
+            wall_bounce_factor,
+        ).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn try_new_independant(
+        local: ParamDist, tribal: ParamDist,
+        inertial: ParamDist, inertial_growth_factor: ParamDist,
+        wall_bounce_factor: ParamDist,
+    ) -> Result<Self, PsoError> {
+        let l = correct_sine_dist(local)?;
+        let t = correct_sine_dist(tribal)?;
+        let i = correct_sine_dist(inertial)?;
 
-        let ifg = correct_sine_dist(inertial_growth_factor);
-        let wbf = correct_sine_dist(wall_bounce_factor);
+        let ifg = correct_sine_dist(inertial_growth_factor)?;
+        let wbf = correct_sine_dist(wall_bounce_factor)?;
 
-        SwarmConfigDistribution{
+        Ok(SwarmConfigDistribution{
             l: l,
             t: t,
             g: ParamDist::Fixed(0.0),
@@ -176,16 +422,56 @@ impl SwarmConfigDistribution {
             igf: ifg,
             wbf: wbf,
             tgse: None,
+            inertia_schedule: InertiaSchedule::default(),
+            stochasticity_schedule: StochasticitySchedule::default(),
+            velocity_update_rule: VelocityUpdateRule::default(),
+            topology: Topology::default(),
+            evolutionary_state_estimation: false,
+        })
+    }
+
+    // every sampled configuration shares the schedule
+    pub fn with_inertia_schedule(mut self, inertia_schedule: InertiaSchedule) -> Self {
+        self.inertia_schedule = inertia_schedule;
+        self
+    }
+
+    pub fn with_stochasticity_schedule(mut self, stochasticity_schedule: StochasticitySchedule) -> Self {
+        self.stochasticity_schedule = stochasticity_schedule;
+        self
+    }
+
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    // sampled configurations are checked when the job starts, as phi varies between samples
+    pub fn with_velocity_update_rule(mut self, velocity_update_rule: VelocityUpdateRule) -> Self {
+        self.velocity_update_rule = velocity_update_rule;
+        if velocity_update_rule.requires_uniform_stochasticity() {
+            self.stochasticity_schedule = StochasticitySchedule::Uniform;
         }
+        self
     }
 
-    pub fn sample_configuration(&self, rng: &mut ThreadRng) -> SwarmConfig {
+    pub fn with_evolutionary_state_estimation(mut self) -> Self {
+        self.evolutionary_state_estimation = true;
+        self
+    }
+
+    pub fn sample_configuration(&self, rng: &mut StdRng) -> SwarmConfig {
 
         SwarmConfig {
             motion_coeffs: [self.l.sample(rng), self.t.sample(rng), self.g.sample(rng), self.i.sample(rng)],
             igf: self.igf.sample(rng),
             wbf: self.wbf.sample(rng),
             tgse: self.tgse,
+            inertia_schedule: self.inertia_schedule,
+            stochasticity_schedule: self.stochasticity_schedule.clone(),
+            velocity_update_rule: self.velocity_update_rule,
+            topology: self.topology,
+            evolutionary_state_estimation: self.evolutionary_state_estimation,
         }
     }
 }
@@ -197,7 +483,7 @@ pub enum ParamDist {
 }
 
 impl ParamDist {
-    pub fn sample(&self, rng: &mut ThreadRng) -> f64 {
+    pub fn sample(&self, rng: &mut StdRng) -> f64 {
         match self {
             Self::Fixed(value) => *value,
             Self::Range(mean_var) => {
@@ -208,15 +494,15 @@ impl ParamDist {
     }
 }
 
-fn correct_sine_dist(param: ParamDist) -> ParamDist {
+fn correct_sine_dist(param: ParamDist) -> Result<ParamDist, PsoError> {
     match param {
-        ParamDist::Fixed(value) => ParamDist::Fixed(correct_sine(value)),
+        ParamDist::Fixed(value) => Ok(ParamDist::Fixed(correct_sine(value))),
         ParamDist::Range(mean_var) => {
-            assert!(mean_var[1] < 1.0 && mean_var[1] > 0.0, 
-                "Parameter Distribution Variance must be between 0 and 1"
-            );
+            if !(mean_var[1] < 1.0 && mean_var[1] > 0.0) {
+                return Err(PsoError::InvalidVariance(mean_var[1]));
+            }
             let mean = correct_sine(mean_var[0]);
-            ParamDist::Range([mean, mean_var[1]])
+            Ok(ParamDist::Range([mean, mean_var[1]]))
         }
     }
 }
@@ -1,7 +1,12 @@
-use rand::prelude::ThreadRng;
+use rand::rngs::StdRng;
+use rand::Rng;
 use rand::distributions::{Distribution, Uniform};
 use std::cmp::Ordering;
 use std::fmt;
+use std::sync::atomic::{self, AtomicUsize};
+use std::sync::Arc;
+
+use super::PsoError;
 
 
 #[derive(Clone, Debug)]
@@ -11,32 +16,50 @@ pub struct Bound {
 }
 
 impl Bound {
-    pub fn from_upper_lower(lims: Vec<[f64;2]>) -> Self {
-        let dists = lims.iter().map(|x| {
-            assert!(x[0] < x[1], "All lower bounds must be less than than upper bounds in lims vector!");
-            Uniform::new(x[0], x[1])
-        }).collect();
+    pub fn from_upper_lower(lims: Vec<[f64;2]>) -> Result<Self, PsoError> {
+        if lims.is_empty() {
+            return Err(PsoError::NoVariables);
+        }
+        let mut dists = Vec::new();
 
-        Bound {
+        for (i, x) in lims.iter().enumerate() {
+            if !x[0].is_finite() || !x[1].is_finite() {
+                return Err(PsoError::NonFiniteBounds { index: i, lower: x[0], upper: x[1] });
+            }
+            if x[0] >= x[1] {
+                return Err(PsoError::InvertedBounds { index: i, lower: x[0], upper: x[1] });
+            }
+            dists.push(Uniform::new(x[0], x[1]));
+        }
+
+        Ok(Bound {
             dists: dists,
             ranges: lims,
-        }
+        })
     }
 
-    pub fn from_max(maxs: Vec<f64>) -> Self {
+    pub fn from_max(maxs: Vec<f64>) -> Result<Self, PsoError> {
+        if maxs.is_empty() {
+            return Err(PsoError::NoVariables);
+        }
         let mut dists = Vec::new();
         let mut ranges = Vec::new();
 
-        for x in maxs {
-            assert!(x > 0.0, "Max values must be posative numbers in max -values vector!");
+        for (i, x) in maxs.into_iter().enumerate() {
+            if !x.is_finite() {
+                return Err(PsoError::NonFiniteVelocity { index: i, value: x });
+            }
+            if x <= 0.0 {
+                return Err(PsoError::NonPositiveVelocity { index: i, value: x });
+            }
             dists.push(Uniform::new(-1.0 * x, x));
             ranges.push([-1.0 * x, x]);
         }
 
-        Bound {dists, ranges}
+        Ok(Bound {dists, ranges})
     }
 
-    pub fn sample_vec(&self, rng: &mut ThreadRng) -> Vec<f64> {
+    pub fn sample_vec(&self, rng: &mut StdRng) -> Vec<f64> {
         self.dists.iter().map(|dist| dist.sample(rng)).collect()
     }
 
@@ -63,30 +86,45 @@ impl fmt::Display for Bound {
 #[derive(Clone, Debug)]
 pub struct Record {
     cost: f64,
+    violation: f64,
+    penalty: Option<f64>,
     location: Vec<f64>
 }
 
 impl Record {
     pub fn blank(num_vars: usize) -> Self {
         Record {
-            cost: std::f64::MAX,
+            cost: f64::MAX,
+            violation: f64::MAX,
+            penalty: None,
             location: vec![0.0; num_vars],
         }
     }
 
     pub fn new(cost: f64, location: &[f64]) -> Self {
-        Record{cost:cost, location: location.to_vec()}
+        Record{cost, violation: 0.0, penalty: None, location: location.to_vec()}
+    }
+
+    // a point of a constrained job: penalised records are compared by cost + penalty, the others by Deb's rules
+    pub fn constrained(cost: f64, violation: f64, penalty: Option<f64>, location: &[f64]) -> Self {
+        Record{cost, violation, penalty, location: location.to_vec()}
+    }
+
+    // reuses the record's own buffer where it can
+    pub fn with_location(mut self, location: &[f64]) -> Self {
+        self.location.clear();
+        self.location.extend_from_slice(location);
+        self
     }
 
     pub fn blind_accumulate(&mut self, other_rec: &Self) {
-        if other_rec.cost < self.cost {
-            self.cost = other_rec.cost;
-            self.location = other_rec.location.clone();
+        if *other_rec < *self {
+            *self = other_rec.clone();
         }
     }
 
-    pub fn loses_to(&self, other_cost: f64) -> bool {
-        other_cost < self.cost
+    pub fn loses_to(&self, other_rec: &Self) -> bool {
+        *other_rec < *self
     }
 
     pub fn to_tuple(&self) -> (f64, Vec<f64>) {
@@ -104,17 +142,63 @@ impl Record {
     pub fn get_cost(&self) -> f64 {
         self.cost
     }
+
+    // total amount by which the point breaks the job's constraints
+    pub fn get_violation(&self) -> f64 {
+        self.violation
+    }
+
+    pub fn is_feasible(&self) -> bool {
+        self.violation == 0.0
+    }
+
+    // feasible points are never penalised, even by an infinite coefficient, and blank records stay blank
+    pub fn penalise(&mut self, coefficient: f64) {
+        if self.is_blank() {
+            return;
+        }
+        self.penalty = match self.violation > 0.0 {
+            true => Some(coefficient * self.violation),
+            false => Some(0.0),
+        };
+    }
+
+    pub fn get_penalised_cost(&self) -> f64 {
+        self.cost + self.penalty.unwrap_or(0.0)
+    }
+
+    // the (violation, cost) pair records are ordered by: a penalised record carries its violation in its cost
+    pub fn get_ranking(&self) -> (f64, f64) {
+        match self.penalty {
+            Some(_) => (0.0, self.get_penalised_cost()),
+            None => (self.violation, self.cost),
+        }
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.cost == f64::MAX
+    }
 }
 
 impl PartialOrd for Record {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.cost.partial_cmp(&other.cost)
+        if self.cost.is_nan() || other.cost.is_nan() {
+            return None;
+        }
+
+        match (self.penalty, other.penalty) {
+            (None, None) => match self.violation.partial_cmp(&other.violation) {
+                Some(Ordering::Equal) => self.cost.partial_cmp(&other.cost),
+                ordering => ordering,
+            },
+            _ => self.get_penalised_cost().partial_cmp(&other.get_penalised_cost()),
+        }
     }
 }
 
 impl PartialEq for Record {
     fn eq(&self, other: &Self) -> bool {
-        self.cost == other.cost
+        self.partial_cmp(other) == Some(Ordering::Equal)
     }
 }
 
@@ -128,37 +212,93 @@ impl fmt::Display for Record {
             write!(f, "{:.5}, ", self.location[i])?;
         }
 
-        write!(f, "{:.5}]", self.location[num_vars])
+        write!(f, "{:.5}]", self.location[num_vars])?;
+
+        match self.is_feasible() || self.is_blank() {
+            true => Ok(()),
+            false => write!(f, ", violating constraints by {}", self.violation),
+        }
     }
 }
 
+// What happens to a particle that leaves the search space
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BoundaryHandling {
+    // clamp to the wall and scale the velocity by the wall bounce factor
+    #[default]
+    Bounce,
+    // mirror the position back inside the wall and reverse the velocity
+    Reflect,
+    // leave through one wall and re-enter through the opposite one
+    Periodic,
+    // re-sample the offending coordinate uniformly inside the bounds
+    Random,
+    // clamp to the wall and stop the particle
+    Absorb,
+    // let the particle fly, but do not evaluate it while it is outside the bounds
+    Infinity,
+}
+
 pub struct SpaceRanger {
     num_variables: usize,
     pos_ranges: Vec<[f64; 2]>,
     vel_ranges: Vec<[f64; 2]>,
     wbf: f64,
+    boundary_handling: BoundaryHandling,
 }
 
 impl SpaceRanger {
-    pub fn new(num_variables: usize, pos_bounds: &Bound, vel_bounds: &Bound, wbf: f64) -> Self {
+    pub fn new(
+        num_variables: usize,
+        pos_bounds: &Bound,
+        vel_bounds: &Bound,
+        wbf: f64,
+        boundary_handling: BoundaryHandling,
+    ) -> Self {
         SpaceRanger {
             num_variables: num_variables,
             pos_ranges: pos_bounds.range_vec(),
             vel_ranges: vel_bounds.range_vec(),
             wbf: wbf,
+            boundary_handling,
         }
     }
 
-    pub fn constrain(&self, pos_vel: &mut[f64]) {
+    pub fn constrain(&self, pos_vel: &mut[f64], rng: &mut StdRng) {
         for i in 0..self.num_variables {
             let iv = i + self.num_variables;
-
-            if pos_vel[i] < self.pos_ranges[i][0] {
-                pos_vel[i] = self.pos_ranges[i][0];
-                pos_vel[iv] = pos_vel[iv] * self.wbf;
-            } else if pos_vel[i] > self.pos_ranges[i][1] {
-                pos_vel[i] = self.pos_ranges[i][1];
-                pos_vel[iv] = pos_vel[iv] * self.wbf;
+            let [lower, upper] = self.pos_ranges[i];
+
+            if pos_vel[i] < lower || pos_vel[i] > upper {
+                match self.boundary_handling {
+                    BoundaryHandling::Bounce => {
+                        pos_vel[i] = pos_vel[i].max(lower).min(upper);
+                        pos_vel[iv] *= self.wbf;
+                    }
+                    BoundaryHandling::Reflect => {
+                        pos_vel[i] = match pos_vel[i] < lower {
+                            true => 2.0 * lower - pos_vel[i],
+                            false => 2.0 * upper - pos_vel[i],
+                        };
+                        // a particle further out than the width of the space lands on the far wall
+                        pos_vel[i] = pos_vel[i].max(lower).min(upper);
+                        pos_vel[iv] = -pos_vel[iv];
+                    }
+                    BoundaryHandling::Periodic => {
+                        pos_vel[i] = lower + (pos_vel[i] - lower).rem_euclid(upper - lower);
+                    }
+                    BoundaryHandling::Random => {
+                        pos_vel[i] = match lower < upper {
+                            true => rng.gen_range(lower, upper),
+                            false => lower,
+                        };
+                    }
+                    BoundaryHandling::Absorb => {
+                        pos_vel[i] = pos_vel[i].max(lower).min(upper);
+                        pos_vel[iv] = 0.0;
+                    }
+                    BoundaryHandling::Infinity => {}
+                }
             }
 
             if pos_vel[iv] < self.vel_ranges[i][0] {
@@ -169,4 +309,92 @@ impl SpaceRanger {
 
         }
     }
-}
\ No newline at end of file
+
+    // only particles left outside the bounds by BoundaryHandling::Infinity are skipped
+    pub fn skips(&self, position: &[f64]) -> bool {
+        self.boundary_handling == BoundaryHandling::Infinity
+            && position
+                .iter()
+                .zip(self.pos_ranges.iter())
+                .any(|(x, [lower, upper])| x < lower || x > upper)
+    }
+}
+
+// Cost-function evaluations shared by every swarm of a job
+#[derive(Clone, Debug)]
+pub struct EvaluationBudget {
+    max: Option<usize>,
+    used: Arc<AtomicUsize>,
+}
+
+impl EvaluationBudget {
+    pub fn new(max_evaluations: Option<usize>) -> Self {
+        EvaluationBudget {
+            max: max_evaluations,
+            used: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn try_take(&self) -> bool {
+        match self.max {
+            Some(max) => self
+                .used
+                .fetch_update(atomic::Ordering::SeqCst, atomic::Ordering::SeqCst, |used| match used < max {
+                    true => Some(used + 1),
+                    false => None,
+                })
+                .is_ok(),
+            // unlimited budgets are never counted, so swarms don't contend on the atomic
+            None => true,
+        }
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        match self.max {
+            Some(max) => self.used.load(atomic::Ordering::SeqCst) >= max,
+            None => false,
+        }
+    }
+}
+
+// Counts consecutive itterations in which the best record failed to improve by more than a relative tolerance:
+// its violation while that changes, otherwise its (penalised) cost
+#[derive(Clone, Debug)]
+pub struct StallMonitor {
+    reference: (f64, f64),
+    count: usize,
+}
+
+impl StallMonitor {
+    pub fn new() -> Self {
+        StallMonitor {
+            reference: (f64::MAX, f64::MAX),
+            count: 0,
+        }
+    }
+
+    pub fn update(&mut self, best: &Record, tolerance: f64) {
+        let ranking = best.get_ranking();
+        let improved = match ranking.0 == self.reference.0 {
+            true => self.reference.1 - ranking.1 > tolerance * self.reference.1.abs(),
+            false => self.reference.0 - ranking.0 > tolerance * self.reference.0.abs(),
+        };
+
+        if improved {
+            self.reference = ranking;
+            self.count = 0;
+        } else {
+            self.count += 1;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl Default for StallMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
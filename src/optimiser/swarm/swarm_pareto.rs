@@ -0,0 +1,256 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
+
+use super::inertia::InertiaWeight;
+use super::particle::Particle;
+use super::swarm_elements::SpaceRanger;
+use super::{
+    diversity, initialise_particles, seeded_rng, velocity_coeffs, EvaluationBudget, JobConfig, PSOConfig,
+    ParetoArchive, ParetoRecord, ParetoSwarmResult, PsoError, StochasticitySchedule, SwarmConfig, TerminationReason,
+};
+
+// Multi-objective swarm (MOPSO): personal bests follow Pareto dominance, and leaders are drawn from an external
+// archive of non-dominated points; collaborative swarms also merge their archive into the job's shared one
+pub struct SwarmPareto {
+    name: String,
+    motion_coeffs: [f64; 4],
+    inertia: InertiaWeight,
+    stochasticity_schedule: StochasticitySchedule,
+    tgse: Option<usize>,
+    budget: EvaluationBudget,
+    job_config: JobConfig,
+    space_ranger: SpaceRanger,
+    rng: StdRng,
+    particles: Vec<Particle>,
+    personal_bests: Vec<Option<ParetoRecord>>,
+    archive: ParetoArchive,
+    global_archive: Option<ParetoArchive>,
+    num_objectives: usize,
+    itteration: usize,
+    evaluations: usize,
+    no_new_record_count: i128,
+    stale_itterations: usize,
+    try_globalise_agian: bool,
+}
+
+impl SwarmPareto {
+    pub fn new(
+        index: usize,
+        config: &SwarmConfig,
+        job_config: JobConfig,
+        pso_config: &PSOConfig,
+        budget: EvaluationBudget,
+        num_objectives: usize,
+    ) -> Self {
+        let mut rng = seeded_rng(pso_config.stream_seed(index + 1));
+        let (mut motion_coeffs, inertia_schedule) = velocity_coeffs(config);
+        let mut inertia = InertiaWeight::new(
+            inertia_schedule,
+            motion_coeffs[3],
+            config.inertial_growth_factor(),
+            &mut rng,
+        );
+        motion_coeffs[3] = inertia.initial(motion_coeffs[3], job_config.max_itterations, &mut rng);
+        let particles = initialise_particles(&job_config, pso_config.nppt, &mut rng);
+
+        SwarmPareto {
+            name: format!("Swarm_{}", index),
+            motion_coeffs,
+            inertia,
+            stochasticity_schedule: config.stochasticity_schedule(),
+            tgse: match config.is_collaborative() {
+                true => Some(config.tribal_global_share_every()),
+                false => None,
+            },
+            budget,
+            space_ranger: SpaceRanger::new(
+                job_config.num_variables,
+                &job_config.pos_bounds,
+                &job_config.vel_bounds,
+                config.wall_bounce_factor(),
+                job_config.boundary_handling,
+            ),
+            personal_bests: vec![None; particles.len()],
+            particles,
+            rng,
+            archive: ParetoArchive::new(job_config.archive_size, job_config.leader_selection),
+            global_archive: None,
+            num_objectives,
+            itteration: 0,
+            evaluations: 0,
+            no_new_record_count: 4,
+            stale_itterations: 0,
+            try_globalise_agian: false,
+            job_config,
+        }
+    }
+
+    // the shared archive is only given to collaborative swarms; the swarm stops with an error as soon as the cost
+    // function returns the wrong number of objectives
+    pub fn run<F>(
+        &mut self,
+        get_cost: Arc<F>,
+        global_archive_lock: Option<Arc<RwLock<ParetoArchive>>>,
+    ) -> Result<ParetoSwarmResult, PsoError>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+    {
+        let start = Instant::now();
+
+        loop {
+            if let Some(termination) = self.termination() {
+                return Ok(self.result(termination, start));
+            }
+
+            self.step(&get_cost, &global_archive_lock)?;
+        }
+    }
+
+    pub fn step<F>(
+        &mut self,
+        get_cost: &Arc<F>,
+        global_archive_lock: &Option<Arc<RwLock<ParetoArchive>>>,
+    ) -> Result<(), PsoError>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+    {
+        let stoch = self.stochasticity_schedule.range(self.itteration, self.job_config.max_itterations);
+
+        self.move_particles(stoch);
+        let is_improved = self.evaluate(get_cost)?;
+
+        if is_improved {
+            self.no_new_record_count -= 1;
+            if self.no_new_record_count < 0 {
+                self.no_new_record_count = 0;
+            }
+            self.stale_itterations = 0;
+        } else {
+            self.no_new_record_count += 1;
+            self.stale_itterations += 1;
+        }
+
+        self.motion_coeffs[3] = self.inertia.next(
+            self.motion_coeffs[3],
+            self.itteration + 1,
+            self.job_config.max_itterations,
+            self.no_new_record_count,
+            &mut self.rng,
+        );
+
+        self.globalise(global_archive_lock);
+
+        self.itteration += 1;
+        Ok(())
+    }
+
+    // a swarm stalls once its archive has accepted nothing new for the job's stall itterations
+    pub fn termination(&self) -> Option<TerminationReason> {
+        if self.itteration >= self.job_config.max_itterations {
+            Some(TerminationReason::MaxItterations)
+        } else if self.budget.is_exhausted() {
+            Some(TerminationReason::EvaluationBudget)
+        } else if self.job_config.stall_itterations.is_some_and(|limit| self.stale_itterations >= limit) {
+            Some(TerminationReason::Stalled)
+        } else if self.job_config.min_diversity.is_some_and(|min| diversity(&self.particles) < min) {
+            Some(TerminationReason::DiversityCollapsed)
+        } else {
+            self.job_config.interruption()
+        }
+    }
+
+    fn move_particles(&mut self, stoch: [f64; 2]) {
+        let rng = &mut self.rng;
+        let archive = &self.archive;
+        let global_archive = &self.global_archive;
+
+        for (p, personal_best) in self.particles.iter_mut().zip(self.personal_bests.iter()) {
+            // nothing pulls a particle before its first evaluation
+            let position = p.position().to_vec();
+            let personal_best = personal_best.as_ref().map_or(&position[..], |record| record.location());
+            let tribal_leader = archive
+                .select_leader(rng)
+                .map_or(personal_best, |record| record.location());
+            let global_leader = global_archive
+                .as_ref()
+                .and_then(|archive| archive.select_leader(rng))
+                .map(|record| record.location());
+
+            p.update_pareto(&self.motion_coeffs, rng, stoch, personal_best, tribal_leader, global_leader);
+
+            self.space_ranger.constrain(p.pos_vel(), rng);
+        }
+    }
+
+    // true if the archive accepted any of the new points
+    fn evaluate<F>(&mut self, get_cost: &Arc<F>) -> Result<bool, PsoError>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+    {
+        let mut is_improved = false;
+
+        for (p, personal_best) in self.particles.iter().zip(self.personal_bests.iter_mut()) {
+            if self.space_ranger.skips(p.position()) {
+                continue;
+            }
+            if !self.budget.try_take() {
+                break;
+            }
+
+            let costs = get_cost(p.position());
+            self.evaluations += 1;
+
+            if costs.len() != self.num_objectives {
+                return Err(PsoError::ObjectiveCountMismatch { expected: self.num_objectives, found: costs.len() });
+            }
+
+            let record = ParetoRecord::new(costs, p.position());
+
+            // mutually non-dominated points replace the personal best half of the time
+            let is_new_best = match personal_best {
+                None => true,
+                Some(best) => {
+                    record.dominates(best) || (!best.dominates(&record) && self.rng.gen::<bool>())
+                }
+            };
+            if is_new_best {
+                *personal_best = Some(record.clone());
+            }
+
+            is_improved |= self.archive.insert(record, &mut self.rng);
+        }
+
+        Ok(is_improved)
+    }
+
+    fn globalise(&mut self, global_archive_lock: &Option<Arc<RwLock<ParetoArchive>>>) {
+        let (global_archive_lock, tgse) = match (global_archive_lock, self.tgse) {
+            (Some(global_archive_lock), Some(tgse)) => (global_archive_lock, tgse),
+            _ => return,
+        };
+
+        if (self.itteration + 1).is_multiple_of(tgse) || self.try_globalise_agian {
+            match global_archive_lock.try_write() {
+                Ok(mut ga_write_ref) => {
+                    ga_write_ref.merge(&self.archive, &mut self.rng);
+                    self.global_archive = Some((*ga_write_ref).clone());
+                    self.try_globalise_agian = false;
+                }
+                Err(_) => self.try_globalise_agian = true,
+            }
+        }
+    }
+
+    pub fn result(&self, termination: TerminationReason, start: Instant) -> ParetoSwarmResult {
+        ParetoSwarmResult {
+            name: self.name.clone(),
+            front: self.archive.members().to_vec(),
+            termination,
+            itterations: self.itteration,
+            evaluations: self.evaluations,
+            duration: start.elapsed(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use super::particle::Particle;
+use super::{EvolutionaryState, Record};
+
+// Read-only snapshots handed out by PsoRun between itterations
+
+pub struct ParticleView<'a> {
+    particle: &'a Particle,
+}
+
+impl<'a> ParticleView<'a> {
+    pub fn position(&self) -> &'a [f64] {
+        self.particle.position()
+    }
+
+    pub fn velocity(&self) -> &'a [f64] {
+        self.particle.velocity()
+    }
+
+    pub fn best(&self) -> &'a Record {
+        self.particle.record()
+    }
+
+    // only tracked for swarms with a non-global topology
+    pub fn neighbourhood_best(&self) -> &'a Record {
+        self.particle.neighbourhood_best()
+    }
+}
+
+// filled in by the swarms themselves, through their view()
+pub struct SwarmView<'a> {
+    pub(super) name: &'a str,
+    pub(super) particles: &'a [Particle],
+    pub(super) tribal_record: &'a Record,
+    pub(super) global_record: &'a Record,
+    pub(super) is_collaborative: bool,
+    pub(super) itteration: usize,
+    pub(super) stall_count: i128,
+    pub(super) motion_coeffs: [f64; 4],
+    pub(super) stochasticity: [f64; 2],
+    pub(super) evolutionary_state: Option<EvolutionaryState>,
+}
+
+impl<'a> SwarmView<'a> {
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn particles(&self) -> Vec<ParticleView<'a>> {
+        self.particles.iter().map(|particle| ParticleView { particle }).collect()
+    }
+
+    pub fn tribal_record(&self) -> &'a Record {
+        self.tribal_record
+    }
+
+    pub fn global_record(&self) -> &'a Record {
+        self.global_record
+    }
+
+    pub fn is_collaborative(&self) -> bool {
+        self.is_collaborative
+    }
+
+    pub fn itteration(&self) -> usize {
+        self.itteration
+    }
+
+    // itterations since the tribal record last improved, as tracked by the swarm
+    pub fn stall_count(&self) -> i128 {
+        self.stall_count
+    }
+
+    pub fn inertia(&self) -> f64 {
+        self.motion_coeffs[3]
+    }
+
+    // local, tribal and global attraction coefficients followed by the inertia, as used by the next update
+    pub fn motion_coeffs(&self) -> [f64; 4] {
+        self.motion_coeffs
+    }
+
+    // range the random factors of the next particle update are drawn from
+    pub fn stochasticity(&self) -> [f64; 2] {
+        self.stochasticity
+    }
+
+    // only estimated for swarms with evolutionary state estimation
+    pub fn evolutionary_state(&self) -> Option<EvolutionaryState> {
+        self.evolutionary_state
+    }
+}
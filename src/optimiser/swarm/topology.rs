@@ -0,0 +1,145 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+
+use super::particle::Particle;
+
+// Which personal bests each particle of a swarm can see
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Topology {
+    // every particle follows the swarm's tribal record (star)
+    #[default]
+    Global,
+    // k neighbours on each side of the particle's index, wrapping around
+    Ring { k: usize },
+    // the four neighbours on a wrapped grid of the particles
+    VonNeumann,
+    // each particle informs k random others, re-drawn after any itteration without a new tribal record
+    Random { k: usize },
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Global => write!(f, "global"),
+            Self::Ring { k } => write!(f, "ring (k = {})", k),
+            Self::VonNeumann => write!(f, "von Neumann"),
+            Self::Random { k } => write!(f, "random adaptive (k = {})", k),
+        }
+    }
+}
+
+// Informant lists for every particle of a swarm: informants[i] holds the particles whose personal bests i sees
+pub struct Neighbourhoods {
+    topology: Topology,
+    informants: Vec<Vec<usize>>,
+}
+
+impl Neighbourhoods {
+    pub fn new(topology: Topology, num_particles: usize, rng: &mut StdRng) -> Self {
+        let informants = match topology {
+            Topology::Global => Vec::new(),
+            Topology::Ring { k } => ring(num_particles, k),
+            Topology::VonNeumann => von_neumann(num_particles),
+            Topology::Random { k } => random(num_particles, k, rng),
+        };
+
+        Neighbourhoods { topology, informants }
+    }
+
+    pub fn is_global(&self) -> bool {
+        self.topology == Topology::Global
+    }
+
+    // the particle itself and every particle informing it; everyone in the swarm for the global topology
+    pub fn informants(&self, particle: usize, num_particles: usize) -> Vec<usize> {
+        if self.is_global() {
+            return (0..num_particles).collect();
+        }
+
+        let mut informants = self.informants[particle].clone();
+        informants.push(particle);
+        informants.sort_unstable();
+        informants.dedup();
+        informants
+    }
+
+    // hand every particle the best personal record among its informants
+    pub fn share(&mut self, particles: &mut [Particle], is_improved: bool, rng: &mut StdRng) {
+        if let Topology::Random { k } = self.topology {
+            if !is_improved {
+                self.informants = random(particles.len(), k, rng);
+            }
+        }
+
+        if self.is_global() {
+            return;
+        }
+
+        let bests: Vec<_> = self
+            .informants
+            .iter()
+            .enumerate()
+            .map(|(i, informants)| {
+                let mut best = particles[i].record().clone();
+                for &j in informants.iter() {
+                    best.blind_accumulate(particles[j].record());
+                }
+                best
+            })
+            .collect();
+
+        for (particle, best) in particles.iter_mut().zip(bests) {
+            particle.set_neighbourhood_best(best);
+        }
+    }
+}
+
+fn ring(num_particles: usize, k: usize) -> Vec<Vec<usize>> {
+    (0..num_particles)
+        .map(|i| {
+            (1..=k)
+                .flat_map(|offset| {
+                    let offset = offset % num_particles;
+                    vec![(i + offset) % num_particles, (i + num_particles - offset) % num_particles]
+                })
+                .filter(|&j| j != i)
+                .collect()
+        })
+        .collect()
+}
+
+// the grid has as many rows as the largest divisor of the particle count not above its square root
+fn von_neumann(num_particles: usize) -> Vec<Vec<usize>> {
+    let rows = (1..=num_particles)
+        .take_while(|r| r * r <= num_particles)
+        .filter(|r| num_particles.is_multiple_of(*r))
+        .last()
+        .unwrap_or(1);
+    let cols = num_particles / rows.max(1);
+
+    (0..num_particles)
+        .map(|i| {
+            let (row, col) = (i / cols, i % cols);
+            vec![
+                ((row + rows - 1) % rows) * cols + col,
+                ((row + 1) % rows) * cols + col,
+                row * cols + (col + cols - 1) % cols,
+                row * cols + (col + 1) % cols,
+            ]
+            .into_iter()
+            .filter(|&j| j != i)
+            .collect()
+        })
+        .collect()
+}
+
+fn random(num_particles: usize, k: usize, rng: &mut StdRng) -> Vec<Vec<usize>> {
+    let mut informants = vec![Vec::new(); num_particles];
+    for informer in 0..num_particles {
+        for _ in 0..k {
+            informants[rng.gen_range(0, num_particles)].push(informer);
+        }
+    }
+    informants
+}